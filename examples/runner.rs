@@ -1,4 +1,4 @@
-use retry::future::repeat::*;
+use retry::future::async_fn::*;
 use retry::prelude::*;
 
 use std::path::{Path, PathBuf};
@@ -19,11 +19,13 @@ pub async fn main() -> Result<()> {
     })
     .repeat::<3>();
 
-    let async_closure = || async {
+    let mut greeted = 0;
+    let async_closure = async || {
         tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-        println!("Hello, world!");
+        greeted += 1;
+        println!("Hello, world! ({greeted})");
     };
-    // async_closure.repeat::<3>().await;
+    async_closure.repeat::<3>(()).await;
     // sleeper.repeat::<3>(3).await?;
     // my_fun.repeat::<3>().await?;
 
@@ -32,7 +34,7 @@ pub async fn main() -> Result<()> {
         val += 1;
         println!("{val}")
     };
-    trial.repeat::<3>().await;
+    trial.repeat::<3>(()).await;
 
     Ok(())
 }
@@ -0,0 +1,119 @@
+//! Counting attempts, and letting the retried function know which attempt it
+//! is on.
+//!
+//! Every retrier takes its count as [`Attempts`]. A plain number is the
+//! number of retries after the first call, so `3` makes up to 4 calls:
+//!
+//! ```rust
+//! use retry::Attempts;
+//!
+//! assert_eq!(Attempts::from(3), Attempts::retries(3));
+//! assert_eq!(Attempts::retries(3), Attempts::total(4));
+//! ```
+//!
+//! Wrapping a function in [`with_attempt`] makes the retriers pass an
+//! [`Attempt`] in front of the usual arguments.
+//!
+//! ```rust
+//! use retry::attempt::{with_attempt, Attempt};
+//! # #[cfg(feature = "futures")]
+//! use retry::future::retry::*;
+//!
+//! async fn fetch(replica: bool, id: u32) -> Result<u32, String> {
+//!     if replica {
+//!         Ok(id)
+//!     } else {
+//!         Err("primary is down".into())
+//!     }
+//! }
+//!
+//! # #[cfg(feature = "futures")]
+//! # #[tokio::main]
+//! # async fn main() {
+//! let fetch = with_attempt(|attempt: Attempt<String>, id: u32| {
+//!     // Fall back to a replica from the third attempt on
+//!     fetch(attempt.number >= 3, id)
+//! });
+//! assert_eq!(fetch.retry::<3>(7).await, Ok(7));
+//! # }
+//! # #[cfg(not(feature = "futures"))]
+//! # fn main() {}
+//! ```
+
+use core::marker::PhantomData;
+use core::time::Duration;
+
+/// How many times a retrier calls the function at most
+///
+/// The first call is always made, so `Attempts::total(0)` is the same as
+/// `Attempts::total(1)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Attempts {
+    pub(crate) retries: usize,
+}
+
+impl Attempts {
+    /// Up to `n` calls in total
+    pub const fn total(n: usize) -> Self {
+        Attempts {
+            retries: n.saturating_sub(1),
+        }
+    }
+
+    /// The first call and up to `n` retries after it
+    pub const fn retries(n: usize) -> Self {
+        Attempts { retries: n }
+    }
+}
+
+impl From<usize> for Attempts {
+    fn from(retries: usize) -> Self {
+        Attempts::retries(retries)
+    }
+}
+
+#[derive(Debug)]
+pub struct Attempt<'a, E> {
+    /// Starts at 1 for the first attempt
+    pub number: usize,
+    /// Time since the first attempt started
+    pub elapsed: Duration,
+    /// The error the previous attempt failed with
+    pub previous_error: Option<&'a E>,
+}
+
+impl<E> Clone for Attempt<'_, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for Attempt<'_, E> {}
+
+/// A function taking an [`Attempt`] before its arguments
+///
+/// Async functions can't keep the attempt across an await point, copy what
+/// you need out of it before creating the future.
+pub struct WithAttempt<F, E> {
+    pub(crate) func: F,
+    error: PhantomData<fn(&E)>,
+}
+
+impl<F: Clone, E> Clone for WithAttempt<F, E> {
+    fn clone(&self) -> Self {
+        with_attempt(self.func.clone())
+    }
+}
+
+impl<F, E> core::fmt::Debug for WithAttempt<F, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WithAttempt").finish_non_exhaustive()
+    }
+}
+
+pub fn with_attempt<F, E>(func: F) -> WithAttempt<F, E> {
+    WithAttempt {
+        func,
+        error: PhantomData,
+    }
+}
@@ -0,0 +1,241 @@
+//! Delays between attempts.
+//!
+//! A [`Backoff`] is any iterator of [`Duration`]s. The retriers take the next
+//! delay after every failed attempt and wait that long before trying again.
+//! Once a backoff runs dry the remaining attempts run back to back.
+//!
+//! ```rust
+//! use core::time::Duration;
+//! use retry::backoff::Exponential;
+//!
+//! let delays: Vec<_> = Exponential::new(Duration::from_millis(100))
+//!     .max_delay(Duration::from_millis(500))
+//!     .take(4)
+//!     .collect();
+//! assert_eq!(delays, [100, 200, 400, 500].map(Duration::from_millis));
+//! ```
+
+use core::time::Duration;
+
+use crate::rng::Rng;
+
+pub trait Backoff: Iterator<Item = Duration> {}
+
+impl<T> Backoff for T where T: Iterator<Item = Duration> {}
+
+/// Don't wait between attempts
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoBackoff;
+
+impl Iterator for NoBackoff {
+    type Item = Duration;
+    fn next(&mut self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Wait the same `delay` between every attempt
+#[derive(Debug, Clone, Copy)]
+pub struct Constant {
+    delay: Duration,
+    max: Duration,
+}
+
+impl Constant {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            max: Duration::MAX,
+        }
+    }
+
+    pub fn max_delay(mut self, max: Duration) -> Self {
+        self.max = max;
+        self
+    }
+}
+
+impl Iterator for Constant {
+    type Item = Duration;
+    fn next(&mut self) -> Option<Duration> {
+        Some(self.delay.min(self.max))
+    }
+}
+
+/// Start at `initial` and grow by `step` after every attempt
+#[derive(Debug, Clone, Copy)]
+pub struct Linear {
+    current: Duration,
+    step: Duration,
+    max: Duration,
+}
+
+impl Linear {
+    pub fn new(initial: Duration, step: Duration) -> Self {
+        Self {
+            current: initial,
+            step,
+            max: Duration::MAX,
+        }
+    }
+
+    pub fn max_delay(mut self, max: Duration) -> Self {
+        self.max = max;
+        self
+    }
+}
+
+impl Iterator for Linear {
+    type Item = Duration;
+    fn next(&mut self) -> Option<Duration> {
+        let delay = self.current.min(self.max);
+        self.current = self.current.saturating_add(self.step);
+        Some(delay)
+    }
+}
+
+/// Start at `initial` and multiply by `factor` (2 by default) after every attempt
+#[derive(Debug, Clone, Copy)]
+pub struct Exponential {
+    current: Duration,
+    factor: u32,
+    max: Duration,
+}
+
+impl Exponential {
+    pub fn new(initial: Duration) -> Self {
+        Self {
+            current: initial,
+            factor: 2,
+            max: Duration::MAX,
+        }
+    }
+
+    pub fn factor(mut self, factor: u32) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    pub fn max_delay(mut self, max: Duration) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Wait a random time between zero and the exponential delay
+    pub fn full_jitter(self) -> Jittered {
+        Jittered::new(self, Jitter::Full)
+    }
+
+    /// Wait half the exponential delay plus a random time up to the other half
+    pub fn equal_jitter(self) -> Jittered {
+        Jittered::new(self, Jitter::Equal)
+    }
+
+    /// Wait a random time between `initial` and three times the previous
+    /// delay, capped at the maximum delay
+    pub fn decorrelated_jitter(self) -> Jittered {
+        Jittered::new(self, Jitter::Decorrelated)
+    }
+}
+
+impl Iterator for Exponential {
+    type Item = Duration;
+    fn next(&mut self) -> Option<Duration> {
+        let delay = self.current.min(self.max);
+        self.current = self.current.saturating_mul(self.factor);
+        Some(delay)
+    }
+}
+
+/// Wait `initial`, `initial`, `2 * initial`, `3 * initial`, `5 * initial`, ...
+#[derive(Debug, Clone, Copy)]
+pub struct Fibonacci {
+    current: Duration,
+    next: Duration,
+    max: Duration,
+}
+
+impl Fibonacci {
+    pub fn new(initial: Duration) -> Self {
+        Self {
+            current: initial,
+            next: initial,
+            max: Duration::MAX,
+        }
+    }
+
+    pub fn max_delay(mut self, max: Duration) -> Self {
+        self.max = max;
+        self
+    }
+}
+
+impl Iterator for Fibonacci {
+    type Item = Duration;
+    fn next(&mut self) -> Option<Duration> {
+        let delay = self.current.min(self.max);
+        let next = self.current.saturating_add(self.next);
+        self.current = core::mem::replace(&mut self.next, next);
+        Some(delay)
+    }
+}
+
+/// How [`Jittered`] randomizes the exponential delays
+///
+/// See <https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jitter {
+    Full,
+    Equal,
+    Decorrelated,
+}
+
+/// [`Exponential`] backoff with randomized delays
+///
+/// The generator is seeded randomly, use [`Jittered::seed`] for a
+/// reproducible sequence of delays.
+#[derive(Debug, Clone)]
+pub struct Jittered {
+    exponential: Exponential,
+    jitter: Jitter,
+    rng: Rng,
+    base: Duration,
+    previous: Duration,
+}
+
+impl Jittered {
+    pub fn new(exponential: Exponential, jitter: Jitter) -> Self {
+        Self {
+            base: exponential.current,
+            previous: exponential.current,
+            exponential,
+            jitter,
+            rng: Rng::random(),
+        }
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+}
+
+impl Iterator for Jittered {
+    type Item = Duration;
+    fn next(&mut self) -> Option<Duration> {
+        let delay = match self.jitter {
+            Jitter::Full => self.rng.between(Duration::ZERO, self.exponential.next()?),
+            Jitter::Equal => {
+                let half = self.exponential.next()? / 2;
+                half.saturating_add(self.rng.between(Duration::ZERO, half))
+            }
+            Jitter::Decorrelated => {
+                let upper = self.previous.saturating_mul(3).max(self.base);
+                let delay = self.rng.between(self.base, upper).min(self.exponential.max);
+                self.previous = delay;
+                delay
+            }
+        };
+        Some(delay)
+    }
+}
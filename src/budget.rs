@@ -0,0 +1,75 @@
+//! Bounding retries to a fraction of the traffic.
+//!
+//! A [`RetryBudget`] is shared between retriers, usually behind an `Arc`.
+//! Every success deposits a fraction of a token and every retry withdraws a
+//! whole one. Once the budget runs dry the retriers stop retrying, so a
+//! failing backend sees little more than the original requests.
+//!
+//! ```rust
+//! use retry::budget::RetryBudget;
+//!
+//! // Up to 2 retries in a burst, then one retry for every 10 successes
+//! let budget = RetryBudget::new(2, 0.1);
+//! assert!(budget.withdraw());
+//! assert!(budget.withdraw());
+//! assert!(!budget.withdraw());
+//!
+//! for _ in 0..10 {
+//!     budget.deposit();
+//! }
+//! assert!(budget.withdraw());
+//! ```
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Thousandths of a token, so deposits can be fractions of a retry
+const UNIT: u64 = 1000;
+
+/// A token bucket shared between retriers, see the [module docs](self)
+#[derive(Debug)]
+pub struct RetryBudget {
+    balance: AtomicU64,
+    max: u64,
+    deposit: u64,
+}
+
+impl RetryBudget {
+    /// A full budget holding up to `max_tokens` retries, where every success
+    /// deposits `ratio` of a token
+    ///
+    /// In the long run retries make up at most `ratio` of the successful
+    /// calls, e.g. `0.1` for 10%.
+    pub fn new(max_tokens: u32, ratio: f32) -> Self {
+        let max = u64::from(max_tokens) * UNIT;
+        RetryBudget {
+            balance: AtomicU64::new(max),
+            max,
+            // Round, as most ratios aren't exact in an f32. Negative and NaN
+            // ratios saturate to 0
+            deposit: (f64::from(ratio) * UNIT as f64).round() as u64,
+        }
+    }
+
+    /// Record a success
+    pub fn deposit(&self) {
+        let _ = self
+            .balance
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |balance| {
+                Some(balance.saturating_add(self.deposit).min(self.max))
+            });
+    }
+
+    /// Take a token for a retry, `false` if the budget doesn't hold one
+    pub fn withdraw(&self) -> bool {
+        self.balance
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |balance| {
+                balance.checked_sub(UNIT)
+            })
+            .is_ok()
+    }
+
+    /// The number of retries the budget currently allows
+    pub fn tokens(&self) -> f64 {
+        self.balance.load(Ordering::Relaxed) as f64 / UNIT as f64
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "futures")]
+pub mod future;
 pub mod repeat;
 pub mod retry;
 pub mod run;
@@ -0,0 +1,684 @@
+//! Builders for async functions.
+//!
+//! Mirrors [`retry`](super::retry) and [`repeat`](super::repeat): configure
+//! the retrier, then `.run(..)` it with the arguments and await the result.
+//!
+//! ```rust
+//! use core::time::Duration;
+//! use retry::backoff::Exponential;
+//! use retry::builder::future::*;
+//!
+//! async fn fetch(id: u32) -> std::io::Result<u32> {
+//!     Ok(id)
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let result = fetch
+//!     .retry(3)
+//!     .backoff(Exponential::new(Duration::from_millis(10)))
+//!     .sleeper(tokio::time::sleep)
+//!     .attempt_timeout(Duration::from_secs(1))
+//!     .run(7)
+//!     .await;
+//! assert_eq!(result.unwrap(), 7);
+//! # }
+//! ```
+
+use core::future::Future;
+use core::time::Duration;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::attempt::{Attempt, Attempts, WithAttempt};
+use crate::backoff::{Backoff, NoBackoff};
+use crate::budget::RetryBudget;
+use crate::call::Call;
+use crate::cancel::CancelToken;
+use crate::circuit::{Breaker, CircuitBreaker, NoBreaker};
+use crate::deadline::Deadline;
+use crate::error::{FromTimeout, LastOutput, Outcome, WithRetryError};
+use crate::future::repeat::{self, *};
+use crate::future::retry;
+use crate::future::sleep::{Sleeper, ThreadSleeper};
+use crate::hint::{Hinted, NoHint, ReadHint};
+use crate::hooks::{AsyncHook, AsyncNotify, Hooks};
+use crate::predicate::{Always, RetryIf};
+use crate::throttle::{AdaptiveThrottle, WithThrottle};
+use crate::tryable::Tryable;
+use crate::tuple::{self, ArgSource, TupleCall};
+
+/// No timeout on single attempts
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoTimeout;
+
+/// A timeout on every attempt, see [`Retrier::attempt_timeout`]
+#[derive(Debug, Clone, Copy)]
+pub struct AttemptTimeout(Duration);
+
+/// Sets the attempt timeout on the future once the output is known
+pub trait ApplyTimeout<Out> {
+    fn apply<F, Args, Fut, B, S, P, M, H>(
+        self,
+        retrier: retry::Retrier<F, Args, Fut, B, S, P, M, H>,
+    ) -> retry::Retrier<F, Args, Fut, B, S, P, M, H>
+    where
+        Fut: Future<Output = Out>,
+        S: Sleeper;
+}
+
+impl<Out> ApplyTimeout<Out> for NoTimeout {
+    fn apply<F, Args, Fut, B, S, P, M, H>(
+        self,
+        retrier: retry::Retrier<F, Args, Fut, B, S, P, M, H>,
+    ) -> retry::Retrier<F, Args, Fut, B, S, P, M, H>
+    where
+        Fut: Future<Output = Out>,
+        S: Sleeper,
+    {
+        retrier
+    }
+}
+
+impl<Out: FromTimeout> ApplyTimeout<Out> for AttemptTimeout {
+    fn apply<F, Args, Fut, B, S, P, M, H>(
+        self,
+        retrier: retry::Retrier<F, Args, Fut, B, S, P, M, H>,
+    ) -> retry::Retrier<F, Args, Fut, B, S, P, M, H>
+    where
+        Fut: Future<Output = Out>,
+        S: Sleeper,
+    {
+        retrier.attempt_timeout(self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[must_use = "retry() does nothing unless you `.run(..)` it"]
+pub struct Retrier<
+    T,
+    B = NoBackoff,
+    S = ThreadSleeper,
+    P = Always,
+    M = LastOutput,
+    H = Hooks,
+    L = NoTimeout,
+    C = NoBreaker,
+    W = NoHint,
+> {
+    retry: usize,
+    func: T,
+    backoff: B,
+    sleeper: S,
+    predicate: P,
+    hint: W,
+    outcome: M,
+    hooks: H,
+    timeout: L,
+    deadline: Option<Deadline>,
+    budget: Option<Arc<RetryBudget>>,
+    cancel: Option<CancelToken>,
+    breaker: C,
+}
+
+impl<T, B, S, P, M, H, L, C, W> Retrier<T, B, S, P, M, H, L, C, W> {
+    /// Wait between attempts according to `backoff`
+    pub fn backoff<B2: Backoff>(self, backoff: B2) -> Retrier<T, B2, S, P, M, H, L, C, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Wait between attempts using the timer of a specific runtime
+    pub fn sleeper<S2: Sleeper>(self, sleeper: S2) -> Retrier<T, B, S2, P, M, H, L, C, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Only retry errors for which `predicate` returns `true`, any other
+    /// error is returned straight away
+    pub fn retry_if<P2>(self, predicate: P2) -> Retrier<T, B, S, P2, M, H, L, C, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Wait for the delay the error asks for instead of the backoff, see
+    /// [`future::retry::Retrier::retry_hint`](crate::future::retry::Retrier::retry_hint)
+    pub fn retry_hint(self) -> Retrier<T, B, S, P, M, H, L, C, Hinted> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: Hinted,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Resolve to a `Result` with a [`RetryError`](crate::RetryError) instead
+    /// of the output of the last attempt
+    pub fn with_retry_error(self) -> Retrier<T, B, S, P, WithRetryError, H, L, C, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: WithRetryError,
+            hooks: self.hooks,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Give up on an attempt that takes longer than `timeout`, see
+    /// [`future::retry::Retrier::attempt_timeout`](crate::future::retry::Retrier::attempt_timeout)
+    pub fn attempt_timeout(
+        self,
+        timeout: Duration,
+    ) -> Retrier<T, B, S, P, M, H, AttemptTimeout, C, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            timeout: AttemptTimeout(timeout),
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Stop retrying once the next attempt would start more than `limit`
+    /// after the first one
+    pub fn max_elapsed(self, limit: Duration) -> Self {
+        Retrier {
+            deadline: Some(Deadline::Elapsed(limit)),
+            ..self
+        }
+    }
+
+    /// Stop retrying once the next attempt would start after `deadline`
+    pub fn deadline(self, deadline: Instant) -> Self {
+        Retrier {
+            deadline: Some(Deadline::At(deadline)),
+            ..self
+        }
+    }
+
+    /// Only retry while `budget` holds tokens, see [`RetryBudget`]
+    pub fn budget(self, budget: impl Into<Arc<RetryBudget>>) -> Self {
+        Retrier {
+            budget: Some(budget.into()),
+            ..self
+        }
+    }
+
+    /// Stop retrying once `token` is cancelled, see [`CancelToken`]
+    pub fn cancel_on(self, token: CancelToken) -> Self {
+        Retrier {
+            cancel: Some(token),
+            ..self
+        }
+    }
+
+    /// Fail fast while `breaker` is open, see
+    /// [`future::retry::Retrier::circuit_breaker`](crate::future::retry::Retrier::circuit_breaker)
+    pub fn circuit_breaker(
+        self,
+        breaker: impl Into<Arc<CircuitBreaker>>,
+    ) -> Retrier<T, B, S, P, M, H, L, Arc<CircuitBreaker>, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: breaker.into(),
+        }
+    }
+
+    /// Shed load with `throttle` before every attempt, see
+    /// [`future::retry::Retrier::throttle`](crate::future::retry::Retrier::throttle)
+    pub fn throttle(
+        self,
+        throttle: impl Into<Arc<AdaptiveThrottle>>,
+    ) -> Retrier<T, B, S, P, M, H, L, WithThrottle<C>, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: (self.breaker, throttle.into()),
+        }
+    }
+
+    fn hooks<H2>(self, hooks: impl FnOnce(H) -> H2) -> Retrier<T, B, S, P, M, H2, L, C, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: hooks(self.hooks),
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Turn the configuration into the future retrying `func` with `args`
+    fn into_future<Args, Fut>(self, args: Args) -> retry::Retrier<T, Args, Fut, B, S, P, M, H, C, W>
+    where
+        Fut: Future,
+        B: Backoff,
+        S: Sleeper,
+        L: ApplyTimeout<Fut::Output>,
+        C: Breaker<Fut::Output>,
+    {
+        let retrier = retry::Retrier::new(Attempts::retries(self.retry), self.func, args)
+            .backoff(self.backoff)
+            .sleeper(self.sleeper)
+            .retry_if(self.predicate)
+            .outcome(self.outcome)
+            .hooks(|_| self.hooks);
+        let retrier = match self.deadline {
+            Some(Deadline::Elapsed(limit)) => retrier.max_elapsed(limit),
+            Some(Deadline::At(deadline)) => retrier.deadline(deadline),
+            None => retrier,
+        };
+        let retrier = match self.budget {
+            Some(budget) => retrier.budget(budget),
+            None => retrier,
+        };
+        let retrier = match self.cancel {
+            Some(token) => retrier.cancel_on(token),
+            None => retrier,
+        };
+        self.timeout
+            .apply(retrier)
+            .breaker(|_| self.breaker)
+            .hint(self.hint)
+    }
+}
+
+type WithHooks<T, B, S, P, M, R, SH, X, L, C, W> = Retrier<T, B, S, P, M, Hooks<R, SH, X>, L, C, W>;
+
+#[allow(clippy::type_complexity)]
+impl<T, B, S, P, M, R, SH, X, L, C, W> WithHooks<T, B, S, P, M, R, SH, X, L, C, W> {
+    /// Call `hook` with the error, the number of the failed attempt and the
+    /// delay before the next one
+    pub fn on_retry<E, R2>(self, hook: R2) -> WithHooks<T, B, S, P, M, R2, SH, X, L, C, W>
+    where
+        R2: FnMut(&E, usize, Duration),
+    {
+        self.hooks(|hooks| hooks.on_retry(hook))
+    }
+
+    /// Like [`on_retry`](Self::on_retry), waiting for the returned future
+    /// before the delay starts
+    pub fn on_retry_async<E, R2, HookFut>(
+        self,
+        hook: R2,
+    ) -> WithHooks<T, B, S, P, M, AsyncHook<R2>, SH, X, L, C, W>
+    where
+        R2: FnMut(&E, usize, Duration) -> HookFut,
+        HookFut: Future<Output = ()> + Send + 'static,
+    {
+        self.hooks(|hooks| hooks.on_retry(AsyncHook(hook)))
+    }
+
+    /// Call `hook` with the number of the attempt that succeeded
+    pub fn on_success<SH2>(self, hook: SH2) -> WithHooks<T, B, S, P, M, R, SH2, X, L, C, W>
+    where
+        SH2: FnMut(usize),
+    {
+        self.hooks(|hooks| hooks.on_success(hook))
+    }
+
+    /// Like [`on_success`](Self::on_success), waiting for the returned future
+    /// before resolving
+    pub fn on_success_async<SH2, HookFut>(
+        self,
+        hook: SH2,
+    ) -> WithHooks<T, B, S, P, M, R, AsyncHook<SH2>, X, L, C, W>
+    where
+        SH2: FnMut(usize) -> HookFut,
+        HookFut: Future<Output = ()> + Send + 'static,
+    {
+        self.hooks(|hooks| hooks.on_success(AsyncHook(hook)))
+    }
+
+    /// Call `hook` with the last error and the number of attempts made once
+    /// the retrier gives up on a failure, for any reason
+    pub fn on_exhausted<E, X2>(self, hook: X2) -> WithHooks<T, B, S, P, M, R, SH, X2, L, C, W>
+    where
+        X2: FnMut(&E, usize),
+    {
+        self.hooks(|hooks| hooks.on_exhausted(hook))
+    }
+
+    /// Like [`on_exhausted`](Self::on_exhausted), waiting for the returned
+    /// future before resolving
+    pub fn on_exhausted_async<E, X2, HookFut>(
+        self,
+        hook: X2,
+    ) -> WithHooks<T, B, S, P, M, R, SH, AsyncHook<X2>, L, C, W>
+    where
+        X2: FnMut(&E, usize) -> HookFut,
+        HookFut: Future<Output = ()> + Send + 'static,
+    {
+        self.hooks(|hooks| hooks.on_exhausted(AsyncHook(hook)))
+    }
+}
+
+pub trait Retry<Args, Fut>: Sized {
+    /// Retry up to `attempts` times, see [`Attempts`]
+    fn retry(self, attempts: impl Into<Attempts>) -> Retrier<Self>;
+}
+
+#[must_use = "repeat() does nothing unless you `.run(..)` it"]
+#[derive(Debug, Clone)]
+pub struct Repeater<T> {
+    repeat: usize,
+    func: T,
+}
+
+pub trait Repeat<Args, Fut>: Sized {
+    /// Call the function `times` times, and at least once
+    fn repeat(self, times: usize) -> Repeater<Self>;
+}
+
+impl<T> Retrier<T> {
+    fn new(attempts: Attempts, func: T) -> Self {
+        Retrier {
+            retry: attempts.retries,
+            func,
+            backoff: NoBackoff,
+            sleeper: ThreadSleeper,
+            predicate: Always,
+            hint: NoHint,
+            outcome: LastOutput,
+            hooks: Hooks::default(),
+            timeout: NoTimeout,
+            breaker: NoBreaker,
+            deadline: None,
+            budget: None,
+            cancel: None,
+        }
+    }
+}
+
+impl<F, Args, Fut, B, S, P, M, H, L, C, W> tuple::Run<Args, Fut>
+    for Retrier<F, B, S, P, M, H, L, C, W>
+where
+    Args: ArgSource,
+    F: Call<Args::Args, <Fut::Output as Tryable>::Error, Output = Fut>,
+    Fut: Future,
+    Fut::Output: Tryable,
+    B: Backoff,
+    S: Sleeper,
+    P: RetryIf<<Fut::Output as Tryable>::Error>,
+    W: ReadHint<<Fut::Output as Tryable>::Error>,
+    M: Outcome<Fut::Output>,
+    H: AsyncNotify<<Fut::Output as Tryable>::Error>,
+    L: ApplyTimeout<Fut::Output>,
+    C: Breaker<Fut::Output>,
+{
+    type Output = retry::Retrier<F, Args, Fut, B, S, P, M, H, C, W>;
+    fn run(self, args: Args) -> Self::Output {
+        self.into_future(args)
+    }
+}
+
+impl<F, Args, Fut> tuple::Run<Args, Fut> for Repeater<F>
+where
+    Args: ArgSource,
+    F: TupleCall<Args::Args, Output = Fut>,
+    Fut: Future,
+{
+    type Output = repeat::Repeater<F, Args, Fut>;
+    fn run(self, args: Args) -> Self::Output {
+        repeat::Repeater::new(self.repeat, self.func, args)
+    }
+}
+
+pub trait Run0<Output> {
+    fn run(self) -> Output;
+}
+
+impl<F, Fut> Retry<(), Fut> for F
+where
+    F: FnMut() -> Fut,
+    Fut: Future,
+{
+    fn retry(self, attempts: impl Into<Attempts>) -> Retrier<Self> {
+        Retrier::new(attempts.into(), self)
+    }
+}
+
+impl<F, E, Fut> Retry<(), Fut> for WithAttempt<F, E>
+where
+    F: FnMut(Attempt<'_, E>) -> Fut,
+    Fut: Future,
+{
+    fn retry(self, attempts: impl Into<Attempts>) -> Retrier<Self> {
+        Retrier::new(attempts.into(), self)
+    }
+}
+
+impl<F, Fut> Repeat<(), Fut> for F
+where
+    F: FnMut() -> Fut,
+    Fut: Future,
+{
+    fn repeat(self, times: usize) -> Repeater<Self> {
+        Repeater {
+            repeat: times,
+            func: self,
+        }
+    }
+}
+
+impl<F, Fut, B, S, P, M, H, L, C, W> Run0<retry::Retrier<F, (), Fut, B, S, P, M, H, C, W>>
+    for Retrier<F, B, S, P, M, H, L, C, W>
+where
+    F: Call<(), <Fut::Output as Tryable>::Error, Output = Fut>,
+    Fut: Future,
+    Fut::Output: Tryable,
+    B: Backoff,
+    S: Sleeper,
+    P: RetryIf<<Fut::Output as Tryable>::Error>,
+    W: ReadHint<<Fut::Output as Tryable>::Error>,
+    M: Outcome<Fut::Output>,
+    H: AsyncNotify<<Fut::Output as Tryable>::Error>,
+    L: ApplyTimeout<Fut::Output>,
+    C: Breaker<Fut::Output>,
+{
+    fn run(self) -> retry::Retrier<F, (), Fut, B, S, P, M, H, C, W> {
+        self.into_future(())
+    }
+}
+
+impl<F, Fut> Run0<repeat::Repeater<F, (), Fut>> for Repeater<F>
+where
+    F: FnMut() -> Fut,
+    Fut: Future,
+{
+    fn run(self) -> repeat::Repeater<F, (), Fut> {
+        AsyncRepeat0::repeat_n(self.func, self.repeat)
+    }
+}
+
+macro_rules! impl_gen_run {
+    ($name: ident, $repeat: ident, $($item: ident),*) => {
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub trait $name<$($item),*, Output> {
+            fn run(self, $($item: $item),*) -> Output;
+        }
+
+        impl<F, Fut, $($item),*> Retry<($($item),*,), Fut> for F
+        where
+            F: FnMut($($item),*) -> Fut,
+            Fut: Future,
+        {
+            fn retry(self, attempts: impl Into<Attempts>) -> Retrier<Self> {
+                Retrier::new(attempts.into(), self)
+            }
+        }
+
+        impl<F, E, Fut, $($item),*> Retry<($($item),*,), Fut> for WithAttempt<F, E>
+        where
+            F: FnMut(Attempt<'_, E>, $($item),*) -> Fut,
+            Fut: Future,
+        {
+            fn retry(self, attempts: impl Into<Attempts>) -> Retrier<Self> {
+                Retrier::new(attempts.into(), self)
+            }
+        }
+
+        impl<F, Fut, $($item),*> Repeat<($($item),*,), Fut> for F
+        where
+            F: FnMut($($item),*) -> Fut,
+            Fut: Future,
+        {
+            fn repeat(self, times: usize) -> Repeater<Self> {
+                Repeater {
+                    repeat: times,
+                    func: self,
+                }
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<F, Fut, B, S, P, M, H, L, C, W, $($item: Clone),*>
+            $name<$($item),*, retry::Retrier<F, ($($item),*,), Fut, B, S, P, M, H, C, W>>
+            for Retrier<F, B, S, P, M, H, L, C, W>
+        where
+            F: Call<($($item),*,), <Fut::Output as Tryable>::Error, Output = Fut>,
+            Fut: Future,
+            Fut::Output: Tryable,
+            B: Backoff,
+            S: Sleeper,
+            P: RetryIf<<Fut::Output as Tryable>::Error>,
+            W: ReadHint<<Fut::Output as Tryable>::Error>,
+            M: Outcome<Fut::Output>,
+            H: AsyncNotify<<Fut::Output as Tryable>::Error>,
+            L: ApplyTimeout<Fut::Output>,
+            C: Breaker<Fut::Output>,
+        {
+            fn run(self, $($item: $item),*) -> retry::Retrier<F, ($($item),*,), Fut, B, S, P, M, H, C, W> {
+                self.into_future(($($item),*,))
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<F, Fut, $($item: Clone),*> $name<$($item),*, repeat::Repeater<F, ($($item),*,), Fut>>
+            for Repeater<F>
+        where
+            F: FnMut($($item),*) -> Fut,
+            Fut: Future,
+        {
+            fn run(self, $($item: $item),*) -> repeat::Repeater<F, ($($item),*,), Fut> {
+                $repeat::repeat_n(self.func, self.repeat, $($item),*)
+            }
+        }
+    };
+}
+
+impl_gen_run!(Run1, AsyncRepeat1, A1);
+impl_gen_run!(Run2, AsyncRepeat2, A1, A2);
+impl_gen_run!(Run3, AsyncRepeat3, A1, A2, A3);
+impl_gen_run!(Run4, AsyncRepeat4, A1, A2, A3, A4);
+impl_gen_run!(Run5, AsyncRepeat5, A1, A2, A3, A4, A5);
+impl_gen_run!(Run6, AsyncRepeat6, A1, A2, A3, A4, A5, A6);
+impl_gen_run!(Run7, AsyncRepeat7, A1, A2, A3, A4, A5, A6, A7);
+impl_gen_run!(Run8, AsyncRepeat8, A1, A2, A3, A4, A5, A6, A7, A8);
+impl_gen_run!(Run9, AsyncRepeat9, A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_gen_run!(
+    Run10,
+    AsyncRepeat10,
+    A1,
+    A2,
+    A3,
+    A4,
+    A5,
+    A6,
+    A7,
+    A8,
+    A9,
+    A10
+);
@@ -6,6 +6,7 @@ pub struct Repeater<T> {
 }
 
 pub trait Repeat<Args, Output>: Sized {
+    /// Call the function `times` times, and at least once
     fn repeat(self, times: usize) -> Repeater<Self>;
 }
 
@@ -1,23 +1,476 @@
+use core::time::Duration;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::attempt::{Attempt, Attempts, WithAttempt};
+use crate::backoff::{Backoff, NoBackoff};
+use crate::budget::RetryBudget;
+use crate::cancel::CancelToken;
+use crate::circuit::{CircuitBreaker, NoBreaker};
+use crate::deadline::Deadline;
+use crate::error::{LastOutput, WithRetryError};
+use crate::hint::{Hinted, NoHint};
+use crate::hooks::Hooks;
+use crate::predicate::Always;
+use crate::throttle::{AdaptiveThrottle, WithThrottle};
+
 #[derive(Debug, Clone)]
 #[must_use = "retry() does nothing unless you `.run(..)` it"]
-pub struct Retrier<T> {
+pub struct Retrier<
+    T,
+    B = NoBackoff,
+    P = Always,
+    M = LastOutput,
+    H = Hooks,
+    C = NoBreaker,
+    W = NoHint,
+> {
     pub(crate) retry: usize,
     pub(crate) func: T,
+    pub(crate) backoff: B,
+    pub(crate) predicate: P,
+    pub(crate) hint: W,
+    pub(crate) outcome: M,
+    pub(crate) hooks: H,
+    pub(crate) deadline: Option<Deadline>,
+    pub(crate) budget: Option<Arc<RetryBudget>>,
+    pub(crate) cancel: Option<CancelToken>,
+    pub(crate) breaker: C,
+}
+
+impl<T, B, P, M, H, C, W> Retrier<T, B, P, M, H, C, W> {
+    /// Wait between attempts according to `backoff`
+    ///
+    /// Every `.run(..)` starts again from the first delay of the backoff.
+    pub fn backoff<B2: Backoff + Clone>(self, backoff: B2) -> Retrier<T, B2, P, M, H, C, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Only retry errors for which `predicate` returns `true`, any other
+    /// error is returned straight away
+    ///
+    /// ```rust
+    /// use retry::builder::{retry::*, run::*};
+    ///
+    /// #[derive(Debug)]
+    /// enum Error {
+    ///     Unavailable,
+    ///     Invalid,
+    /// }
+    ///
+    /// fn validate() -> Result<(), Error> {
+    ///     Err(Error::Invalid)
+    /// }
+    ///
+    /// let result = validate
+    ///     .retry(3)
+    ///     .retry_if(|e: &Error| matches!(e, Error::Unavailable))
+    ///     .run();
+    /// assert!(matches!(result, Err(Error::Invalid)));
+    /// ```
+    pub fn retry_if<P2>(self, predicate: P2) -> Retrier<T, B, P2, M, H, C, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Wait for the delay the error asks for, see
+    /// [`RetryHint`](crate::hint::RetryHint), instead of the next one of the
+    /// backoff
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use retry::backoff::Constant;
+    /// use retry::builder::{retry::*, run::*};
+    /// use retry::hint::RetryHint;
+    ///
+    /// #[derive(Debug)]
+    /// struct TooManyRequests(Option<Duration>);
+    ///
+    /// impl RetryHint for TooManyRequests {
+    ///     fn retry_after(&self) -> Option<Duration> {
+    ///         self.0
+    ///     }
+    /// }
+    ///
+    /// fn fetch() -> Result<(), TooManyRequests> {
+    ///     Err(TooManyRequests(Some(Duration::from_millis(1))))
+    /// }
+    ///
+    /// let mut delays = vec![];
+    /// let _ = fetch
+    ///     .retry(2)
+    ///     .backoff(Constant::new(Duration::from_secs(60)))
+    ///     .retry_hint()
+    ///     .on_retry(|_: &TooManyRequests, _, delay| delays.push(delay))
+    ///     .run();
+    /// assert_eq!(delays, [Duration::from_millis(1); 2]);
+    /// ```
+    pub fn retry_hint(self) -> Retrier<T, B, P, M, H, C, Hinted> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            predicate: self.predicate,
+            hint: Hinted,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Make `.run(..)` return a `Result` with a [`RetryError`](crate::RetryError)
+    /// instead of the output of the last attempt
+    pub fn with_retry_error(self) -> Retrier<T, B, P, WithRetryError, H, C, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: WithRetryError,
+            hooks: self.hooks,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Stop retrying once the next attempt would start more than `limit`
+    /// after the first one
+    ///
+    /// The retrier gives up before sleeping past the limit and reports
+    /// [`RetryError::TimedOut`](crate::RetryError::TimedOut) with
+    /// [`with_retry_error`](Self::with_retry_error). The limit applies to
+    /// each `.run(..)` separately.
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use retry::backoff::Constant;
+    /// use retry::builder::{retry::*, run::*};
+    /// use retry::RetryError;
+    ///
+    /// fn connect() -> Result<(), String> {
+    ///     Err("connection refused".into())
+    /// }
+    ///
+    /// let result = connect
+    ///     .retry(10)
+    ///     .backoff(Constant::new(Duration::from_millis(20)))
+    ///     .max_elapsed(Duration::from_millis(50))
+    ///     .with_retry_error()
+    ///     .run();
+    /// assert_eq!(result, Err(RetryError::TimedOut));
+    /// ```
+    pub fn max_elapsed(self, limit: Duration) -> Self {
+        Retrier {
+            deadline: Some(Deadline::Elapsed(limit)),
+            ..self
+        }
+    }
+
+    /// Stop retrying once the next attempt would start after `deadline`, see
+    /// [`max_elapsed`](Self::max_elapsed)
+    pub fn deadline(self, deadline: Instant) -> Self {
+        Retrier {
+            deadline: Some(Deadline::At(deadline)),
+            ..self
+        }
+    }
+
+    /// Only retry while `budget` holds tokens, depositing into it on success
+    ///
+    /// Once the budget refuses a retry the retrier gives up and reports
+    /// [`RetryError::Throttled`](crate::RetryError::Throttled) with
+    /// [`with_retry_error`](Self::with_retry_error). Share one
+    /// [`RetryBudget`] between retriers by passing clones of an `Arc`.
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use retry::budget::RetryBudget;
+    /// use retry::builder::{retry::*, run::*};
+    /// use retry::RetryError;
+    ///
+    /// fn fetch() -> Result<(), String> {
+    ///     Err("unavailable".into())
+    /// }
+    ///
+    /// let budget = Arc::new(RetryBudget::new(2, 0.1));
+    /// let result = fetch.retry(5).budget(budget.clone()).with_retry_error().run();
+    /// assert!(matches!(result, Err(RetryError::Throttled { attempts: 3, .. })));
+    /// ```
+    pub fn budget(self, budget: impl Into<Arc<RetryBudget>>) -> Self {
+        Retrier {
+            budget: Some(budget.into()),
+            ..self
+        }
+    }
+
+    /// Stop retrying once `token` is cancelled, from another thread or from
+    /// a hook
+    ///
+    /// The retrier checks the token before every sleep and every retry, so
+    /// the first attempt is always made. It hands back the output of the
+    /// last attempt and reports
+    /// [`RetryError::Cancelled`](crate::RetryError::Cancelled) with
+    /// [`with_retry_error`](Self::with_retry_error).
+    ///
+    /// ```rust
+    /// use retry::builder::{retry::*, run::*};
+    /// use retry::cancel::CancelToken;
+    /// use retry::RetryError;
+    ///
+    /// fn fetch() -> Result<(), String> {
+    ///     Err("unavailable".into())
+    /// }
+    ///
+    /// let token = CancelToken::new();
+    /// let handle = token.clone();
+    /// let result = fetch
+    ///     .retry(5)
+    ///     .cancel_on(token)
+    ///     .on_retry(move |_: &String, _, _| handle.cancel())
+    ///     .with_retry_error()
+    ///     .run();
+    /// assert_eq!(result, Err(RetryError::Cancelled));
+    /// ```
+    pub fn cancel_on(self, token: CancelToken) -> Self {
+        Retrier {
+            cancel: Some(token),
+            ..self
+        }
+    }
+
+    /// Fail fast while `breaker` is open, recording the outcome of every
+    /// attempt in it
+    ///
+    /// An attempt refused by the breaker stops the retrier, which hands back
+    /// the output of the last attempt, or one built with
+    /// [`FromCircuitOpen`](crate::error::FromCircuitOpen) when no attempt
+    /// was made, and reports
+    /// [`RetryError::CircuitOpen`](crate::RetryError::CircuitOpen) with
+    /// [`with_retry_error`](Self::with_retry_error).
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use std::sync::Arc;
+    /// use retry::builder::{retry::*, run::*};
+    /// use retry::circuit::CircuitBreaker;
+    /// use retry::error::CircuitOpen;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Error {
+    ///     Unavailable,
+    ///     CircuitOpen,
+    /// }
+    ///
+    /// impl From<CircuitOpen> for Error {
+    ///     fn from(_: CircuitOpen) -> Self {
+    ///         Error::CircuitOpen
+    ///     }
+    /// }
+    ///
+    /// fn fetch() -> Result<(), Error> {
+    ///     Err(Error::Unavailable)
+    /// }
+    ///
+    /// let breaker = Arc::new(CircuitBreaker::consecutive_failures(2, Duration::from_secs(30)));
+    /// let mut retrier = fetch.retry(5).circuit_breaker(breaker.clone());
+    /// assert_eq!(retrier.run(), Err(Error::Unavailable));
+    /// assert_eq!(retrier.run(), Err(Error::CircuitOpen));
+    /// ```
+    pub fn circuit_breaker(
+        self,
+        breaker: impl Into<Arc<CircuitBreaker>>,
+    ) -> Retrier<T, B, P, M, H, Arc<CircuitBreaker>, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: breaker.into(),
+        }
+    }
+
+    /// Consult `throttle` before every attempt and record its outcome there,
+    /// on top of any circuit breaker
+    ///
+    /// An attempt the throttle rejects stops the retrier like an open
+    /// circuit, building the output with
+    /// [`FromThrottled`](crate::error::FromThrottled) when no attempt was
+    /// made, and reports
+    /// [`RetryError::Throttled`](crate::RetryError::Throttled).
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use std::sync::Arc;
+    /// use retry::builder::{retry::*, run::*};
+    /// use retry::circuit::CircuitBreaker;
+    /// use retry::throttle::AdaptiveThrottle;
+    ///
+    /// fn fetch() -> std::io::Result<u16> {
+    ///     Ok(200)
+    /// }
+    ///
+    /// let breaker = Arc::new(CircuitBreaker::consecutive_failures(5, Duration::from_secs(30)));
+    /// let throttle = Arc::new(AdaptiveThrottle::default());
+    /// let mut retrier = fetch
+    ///     .retry(3)
+    ///     .circuit_breaker(breaker)
+    ///     .throttle(throttle.clone());
+    /// assert_eq!(retrier.run().unwrap(), 200);
+    /// assert_eq!(throttle.rejection_probability(), 0.0);
+    /// ```
+    pub fn throttle(
+        self,
+        throttle: impl Into<Arc<AdaptiveThrottle>>,
+    ) -> Retrier<T, B, P, M, H, WithThrottle<C>, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: (self.breaker, throttle.into()),
+        }
+    }
+
+    fn hooks<H2>(self, hooks: impl FnOnce(H) -> H2) -> Retrier<T, B, P, M, H2, C, W> {
+        Retrier {
+            retry: self.retry,
+            func: self.func,
+            backoff: self.backoff,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: hooks(self.hooks),
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            breaker: self.breaker,
+        }
+    }
+}
+
+impl<T, B, P, M, R, S, X, C, W> Retrier<T, B, P, M, Hooks<R, S, X>, C, W> {
+    /// Call `hook` with the error, the number of the failed attempt and the
+    /// delay before the next one
+    ///
+    /// ```rust
+    /// use retry::builder::{retry::*, run::*};
+    ///
+    /// fn connect() -> Result<(), String> {
+    ///     Err("connection refused".into())
+    /// }
+    ///
+    /// let _ = connect
+    ///     .retry(3)
+    ///     .on_retry(|e: &String, attempt, delay| eprintln!("attempt {attempt} failed: {e}, retrying in {delay:?}"))
+    ///     .on_exhausted(|e: &String, attempts| eprintln!("giving up after {attempts} attempts: {e}"))
+    ///     .run();
+    /// ```
+    pub fn on_retry<E, R2>(self, hook: R2) -> Retrier<T, B, P, M, Hooks<R2, S, X>, C, W>
+    where
+        R2: FnMut(&E, usize, Duration),
+    {
+        self.hooks(|hooks| hooks.on_retry(hook))
+    }
+
+    /// Call `hook` with the number of the attempt that succeeded
+    pub fn on_success<S2>(self, hook: S2) -> Retrier<T, B, P, M, Hooks<R, S2, X>, C, W>
+    where
+        S2: FnMut(usize),
+    {
+        self.hooks(|hooks| hooks.on_success(hook))
+    }
+
+    /// Call `hook` with the last error and the number of attempts made
+    /// whenever the retrier gives up on a failure: out of attempts, on a
+    /// permanent error, at the deadline, once cancelled, or refused by the
+    /// budget, circuit breaker or throttle
+    pub fn on_exhausted<E, X2>(self, hook: X2) -> Retrier<T, B, P, M, Hooks<R, S, X2>, C, W>
+    where
+        X2: FnMut(&E, usize),
+    {
+        self.hooks(|hooks| hooks.on_exhausted(hook))
+    }
 }
 
 pub trait Retry<Args, Output>: Sized {
-    fn retry(self, times: usize) -> Retrier<Self>;
+    /// Retry up to `attempts` times, see [`Attempts`]
+    fn retry(self, attempts: impl Into<Attempts>) -> Retrier<Self>;
+}
+
+impl<T> Retrier<T> {
+    fn new(attempts: Attempts, func: T) -> Self {
+        Retrier {
+            retry: attempts.retries,
+            func,
+            backoff: NoBackoff,
+            predicate: Always,
+            hint: NoHint,
+            outcome: LastOutput,
+            hooks: Hooks::default(),
+            deadline: None,
+            budget: None,
+            cancel: None,
+            breaker: NoBreaker,
+        }
+    }
 }
 
 impl<F, O> Retry<(), O> for F
 where
     F: FnMut() -> O,
 {
-    fn retry(self, times: usize) -> Retrier<Self> {
-        Retrier {
-            retry: times,
-            func: self,
-        }
+    fn retry(self, attempts: impl Into<Attempts>) -> Retrier<Self> {
+        Retrier::new(attempts.into(), self)
+    }
+}
+
+impl<F, E, O> Retry<(), O> for WithAttempt<F, E>
+where
+    F: FnMut(Attempt<'_, E>) -> O,
+{
+    fn retry(self, attempts: impl Into<Attempts>) -> Retrier<Self> {
+        Retrier::new(attempts.into(), self)
     }
 }
 
@@ -27,11 +480,17 @@ macro_rules! impl_for_tuple {
         where
             F: FnMut($($tup),*) -> O,
         {
-            fn retry(self, times: usize) -> Retrier<Self> {
-                Retrier {
-                    retry: times,
-                    func: self,
-                }
+            fn retry(self, attempts: impl Into<Attempts>) -> Retrier<Self> {
+                Retrier::new(attempts.into(), self)
+            }
+        }
+
+        impl<F, E, $($tup),*, O> Retry<($($tup),*,), O> for WithAttempt<F, E>
+        where
+            F: FnMut(Attempt<'_, E>, $($tup),*) -> O,
+        {
+            fn retry(self, attempts: impl Into<Attempts>) -> Retrier<Self> {
+                Retrier::new(attempts.into(), self)
             }
         }
         )
@@ -1,6 +1,139 @@
+use std::time::Instant;
+
 use super::repeat::*;
 use super::retry::*;
+
+use crate::attempt::{Attempt, WithAttempt};
+use crate::backoff::Backoff;
+use crate::call::Call;
+use crate::cancel::cancelled;
+use crate::circuit::Breaker;
+use crate::error::{Outcome, Stop};
+use crate::hint::ReadHint;
+use crate::hooks::Notify;
+use crate::predicate::RetryIf;
 use crate::tryable::Tryable;
+use crate::tuple::{ArgSource, Repeat, Run, TupleCall};
+
+impl<T, B, P, M, H, C, W> Retrier<T, B, P, M, H, C, W> {
+    /// Call `attempt` until it succeeds, fails with an error the predicate
+    /// rejects, runs out of attempts, is cancelled or is refused by the
+    /// circuit breaker or throttle, sleeping for the next delay of the backoff in between
+    fn retry_with<Output>(
+        &mut self,
+        mut attempt: impl FnMut(&mut T, Attempt<'_, Output::Error>) -> Output,
+    ) -> (Output, Stop)
+    where
+        B: Backoff + Clone,
+        P: RetryIf<Output::Error>,
+        W: ReadHint<Output::Error>,
+        H: Notify<Output::Error>,
+        C: Breaker<Output>,
+        Output: Tryable,
+    {
+        let mut retry = self.retry;
+        let mut attempts = 0;
+        let mut delays = self.backoff.clone();
+        let started = Instant::now();
+        let mut previous: Option<Output> = None;
+        let (res, stop) = loop {
+            if let Err((refused, stop)) = self.breaker.admit(attempts) {
+                break (previous.unwrap_or(refused), stop);
+            }
+            let res = attempt(
+                &mut self.func,
+                Attempt {
+                    number: attempts + 1,
+                    elapsed: started.elapsed(),
+                    previous_error: previous.as_ref().and_then(Tryable::error),
+                },
+            );
+            attempts += 1;
+            self.breaker.record(&res);
+            match res.error() {
+                None => {
+                    if let Some(budget) = &self.budget {
+                        budget.deposit();
+                    }
+                    self.hooks.success(attempts);
+                    return (res, Stop::Success);
+                }
+                Some(error) if !self.predicate.should_retry(error) => break (res, Stop::Permanent),
+                Some(error) => {
+                    if retry == 0 {
+                        break (res, Stop::Exhausted { attempts });
+                    }
+                    retry -= 1;
+                    let delay = match self.hint.retry_after(error) {
+                        Some(delay) => delay,
+                        None => delays.next().unwrap_or_default(),
+                    };
+                    if let Some(deadline) = self.deadline {
+                        if deadline.crossed_by(started, delay) {
+                            break (res, Stop::TimedOut);
+                        }
+                    }
+                    if cancelled(&self.cancel) {
+                        break (res, Stop::Cancelled);
+                    }
+                    if self
+                        .budget
+                        .as_ref()
+                        .is_some_and(|budget| !budget.withdraw())
+                    {
+                        break (res, Stop::Throttled { attempts });
+                    }
+                    self.hooks.retry(error, attempts, delay);
+                    if !delay.is_zero() {
+                        std::thread::sleep(delay);
+                    }
+                    if cancelled(&self.cancel) {
+                        break (res, Stop::Cancelled);
+                    }
+                }
+            }
+            previous = Some(res);
+        };
+        // Every way of stopping short of a success gives up on an error
+        if let Some(error) = res.error() {
+            self.hooks.exhausted(error, attempts);
+        }
+        (res, stop)
+    }
+}
+
+impl<F, Args, B, P, M, H, C, W, Output> Run<Args, Output> for &mut Retrier<F, B, P, M, H, C, W>
+where
+    Args: ArgSource,
+    F: Call<Args::Args, Output::Error, Output = Output>,
+    B: Backoff + Clone,
+    P: RetryIf<Output::Error>,
+    W: ReadHint<Output::Error>,
+    M: Outcome<Output>,
+    H: Notify<Output::Error>,
+    C: Breaker<Output>,
+    Output: Tryable,
+{
+    type Output = M::Output;
+    fn run(self, mut args: Args) -> M::Output {
+        let (output, stop) = self.retry_with(|func, attempt| {
+            let args = args.args(attempt.number);
+            func.call(attempt, args)
+        });
+        M::report(output, stop)
+    }
+}
+
+impl<F, Args, Output> Run<Args, Output> for &mut Repeater<F>
+where
+    Args: ArgSource,
+    F: TupleCall<Args::Args, Output = Output>,
+{
+    type Output = Output;
+    fn run(self, args: Args) -> Output {
+        self.func.repeat_n(self.repeat, args)
+    }
+}
 
 macro_rules! impl_gen_retry {
     ($name: ident, $($item: ident),*) => {
@@ -9,41 +142,47 @@ macro_rules! impl_gen_retry {
             fn run(&mut self, $($item: $item),*) -> Output;
         }
         #[allow(non_snake_case)]
-        impl<F, $($item: Clone),*, Output> $name<$($item),*, Output> for Retrier<F>
+        impl<F, B, P, M, H, C, W, $($item: Clone),*, Output> $name<$($item),*, M::Output> for Retrier<F, B, P, M, H, C, W>
         where
-            F: Fn($($item),*) -> Output,
+            F: FnMut($($item),*) -> Output,
+            B: Backoff + Clone,
+            P: RetryIf<Output::Error>,
+            W: ReadHint<Output::Error>,
+            M: Outcome<Output>,
+            H: Notify<Output::Error>,
+            C: Breaker<Output>,
             Output: Tryable,
         {
-            fn run(&mut self, $($item: $item),*) -> Output {
-                let mut retry = self.retry;
-                loop {
-                    let res = (self.func)($($item.clone()),*);
-                    if res.negative() {
-                        retry -= 1;
-                    } else {
-                        return res;
-                    }
-                    if retry == 0 {
-                        return res;
-                    }
-                }
+            fn run(&mut self, $($item: $item),*) -> M::Output {
+                Run::run(self, ($($item,)*))
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<F, E, B, P, M, H, C, W, $($item: Clone),*, Output> $name<$($item),*, M::Output>
+            for Retrier<WithAttempt<F, E>, B, P, M, H, C, W>
+        where
+            F: FnMut(Attempt<'_, E>, $($item),*) -> Output,
+            B: Backoff + Clone,
+            P: RetryIf<E>,
+            W: ReadHint<E>,
+            M: Outcome<Output>,
+            H: Notify<E>,
+            C: Breaker<Output>,
+            Output: Tryable<Error = E>,
+        {
+            fn run(&mut self, $($item: $item),*) -> M::Output {
+                Run::run(self, ($($item,)*))
             }
         }
 
         #[allow(non_snake_case)]
         impl<F, $($item: Clone),*, Output> $name<$($item),*, Output> for Repeater<F>
         where
-            F: Fn($($item),*) -> Output,
+            F: FnMut($($item),*) -> Output,
         {
             fn run(&mut self, $($item: $item),*) -> Output {
-                let mut repeat = self.repeat;
-                loop {
-                    let res = (self.func)($($item.clone()),*);
-                    repeat -= 1;
-                    if repeat == 0 {
-                        return res;
-                    }
-                }
+                Run::run(self, ($($item,)*))
             }
         }
     };
@@ -64,24 +203,36 @@ pub trait Run0<Output> {
     fn run(&mut self) -> Output;
 }
 
-impl<F, Output> Run0<Output> for Retrier<F>
+impl<F, B, P, M, H, C, W, Output> Run0<M::Output> for Retrier<F, B, P, M, H, C, W>
 where
     F: FnMut() -> Output,
+    B: Backoff + Clone,
+    P: RetryIf<Output::Error>,
+    W: ReadHint<Output::Error>,
+    M: Outcome<Output>,
+    H: Notify<Output::Error>,
+    C: Breaker<Output>,
     Output: Tryable,
 {
-    fn run(&mut self) -> Output {
-        let mut retry = self.retry;
-        loop {
-            let res = (self.func)();
-            if res.negative() {
-                retry -= 1;
-            } else {
-                return res;
-            }
-            if retry == 0 {
-                return res;
-            }
-        }
+    fn run(&mut self) -> M::Output {
+        Run::run(self, ())
+    }
+}
+
+impl<F, E, B, P, M, H, C, W, Output> Run0<M::Output>
+    for Retrier<WithAttempt<F, E>, B, P, M, H, C, W>
+where
+    F: FnMut(Attempt<'_, E>) -> Output,
+    B: Backoff + Clone,
+    P: RetryIf<E>,
+    W: ReadHint<E>,
+    M: Outcome<Output>,
+    H: Notify<E>,
+    C: Breaker<Output>,
+    Output: Tryable<Error = E>,
+{
+    fn run(&mut self) -> M::Output {
+        Run::run(self, ())
     }
 }
 
@@ -90,13 +241,6 @@ where
     F: FnMut() -> Output,
 {
     fn run(&mut self) -> Output {
-        let mut repeat = self.repeat;
-        loop {
-            let res = (self.func)();
-            repeat -= 1;
-            if repeat == 0 {
-                return res;
-            }
-        }
+        Run::run(self, ())
     }
 }
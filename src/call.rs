@@ -0,0 +1,48 @@
+use crate::attempt::{Attempt, WithAttempt};
+
+/// Call a function with its arguments packed in a tuple, passing the
+/// [`Attempt`] along to functions that asked for it
+pub trait Call<Args, E> {
+    type Output;
+    fn call(&mut self, attempt: Attempt<'_, E>, args: Args) -> Self::Output;
+}
+
+macro_rules! impl_call {
+    ($($item: ident),*) => {
+        #[allow(non_snake_case)]
+        impl<F, O, E, $($item),*> Call<($($item,)*), E> for F
+        where
+            F: FnMut($($item),*) -> O,
+        {
+            type Output = O;
+            fn call(&mut self, _: Attempt<'_, E>, ($($item,)*): ($($item,)*)) -> O {
+                self($($item),*)
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<F, O, E, $($item),*> Call<($($item,)*), E> for WithAttempt<F, E>
+        where
+            F: FnMut(Attempt<'_, E>, $($item),*) -> O,
+        {
+            type Output = O;
+            fn call(&mut self, attempt: Attempt<'_, E>, ($($item,)*): ($($item,)*)) -> O {
+                (self.func)(attempt, $($item),*)
+            }
+        }
+    };
+}
+
+impl_call!();
+impl_call!(A1);
+impl_call!(A1, A2);
+impl_call!(A1, A2, A3);
+impl_call!(A1, A2, A3, A4);
+impl_call!(A1, A2, A3, A4, A5);
+impl_call!(A1, A2, A3, A4, A5, A6);
+impl_call!(A1, A2, A3, A4, A5, A6, A7);
+impl_call!(A1, A2, A3, A4, A5, A6, A7, A8);
+impl_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
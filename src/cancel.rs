@@ -0,0 +1,45 @@
+//! Stopping a retrier from the outside.
+//!
+//! A [`CancelToken`] is cloned into a retrier with `.cancel_on(..)` on the
+//! builder and async retriers. Once any clone is cancelled the retrier stops
+//! before its next sleep or retry, hands back the output of the last attempt
+//! and reports [`RetryError::Cancelled`](crate::RetryError::Cancelled). The
+//! first attempt is always made.
+//!
+//! ```rust
+//! use retry::cancel::CancelToken;
+//!
+//! let token = CancelToken::new();
+//! let handle = token.clone();
+//! assert!(!token.is_cancelled());
+//! handle.cancel();
+//! assert!(token.is_cancelled());
+//! ```
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag shared between its clones, see the [module docs](self)
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop every retrier watching a clone of this token
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+/// Whether the token of a retrier, if it has one, was cancelled
+#[cfg(any(feature = "builder", feature = "futures"))]
+pub(crate) fn cancelled(token: &Option<CancelToken>) -> bool {
+    token.as_ref().is_some_and(CancelToken::is_cancelled)
+}
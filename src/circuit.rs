@@ -0,0 +1,293 @@
+//! Failing fast while a dependency is down.
+//!
+//! A [`CircuitBreaker`] watches the outcome of calls, classified with
+//! [`Tryable`], and trips once too many of them fail:
+//!
+//! - closed: calls go through and their outcomes are recorded
+//! - open: calls are refused until the cool-down has passed
+//! - half-open: a few probe calls go through, the circuit closes again once
+//!   they all succeed and opens again as soon as one fails
+//!
+//! The breaker is shared between retriers behind an `Arc` and attached with
+//! `.circuit_breaker(..)` on the builder and async retriers. An attempt
+//! refused by an open circuit fails with a
+//! [`CircuitOpen`](crate::error::CircuitOpen) error, see [`FromCircuitOpen`].
+//!
+//! ```rust
+//! use core::time::Duration;
+//! use retry::circuit::{CircuitBreaker, State};
+//!
+//! let breaker = CircuitBreaker::consecutive_failures(2, Duration::from_millis(10));
+//! breaker.record(&Err::<(), _>("refused"));
+//! breaker.record(&Err::<(), _>("refused"));
+//! assert_eq!(breaker.state(), State::Open);
+//! assert!(!breaker.allow());
+//!
+//! std::thread::sleep(Duration::from_millis(10));
+//! assert!(breaker.allow());
+//! breaker.record(&Ok::<_, &str>(()));
+//! assert_eq!(breaker.state(), State::Closed);
+//! ```
+
+use core::time::Duration;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use crate::error::{FromCircuitOpen, Stop};
+use crate::tryable::Tryable;
+
+/// The state of a [`CircuitBreaker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum State {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// When a closed circuit trips
+#[derive(Debug, Clone, Copy)]
+enum Trip {
+    Consecutive(usize),
+    Rate { rate: f32, window: usize },
+}
+
+#[derive(Debug)]
+enum Circuit {
+    Closed,
+    /// Open until the end of the cool-down, or for good if it's past what
+    /// an `Instant` can hold
+    Open {
+        until: Option<Instant>,
+    },
+    HalfOpen {
+        admitted: usize,
+        succeeded: usize,
+        last: Instant,
+    },
+}
+
+#[derive(Debug)]
+struct Inner {
+    circuit: Circuit,
+    consecutive: usize,
+    /// Outcomes of the latest calls while closed, `true` for failures
+    window: VecDeque<bool>,
+}
+
+/// A circuit breaker shared between retriers, see the [module docs](self)
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    trip: Trip,
+    cool_down: Duration,
+    probes: usize,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    fn new(trip: Trip, cool_down: Duration) -> Self {
+        CircuitBreaker {
+            trip,
+            cool_down,
+            probes: 1,
+            inner: Mutex::new(Inner {
+                circuit: Circuit::Closed,
+                consecutive: 0,
+                window: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Open after `failures` failed calls in a row, for `cool_down`
+    pub fn consecutive_failures(failures: usize, cool_down: Duration) -> Self {
+        Self::new(Trip::Consecutive(failures.max(1)), cool_down)
+    }
+
+    /// Open once at least `rate` of the last `window` calls failed, for
+    /// `cool_down`
+    ///
+    /// The circuit stays closed until `window` calls have been recorded, and
+    /// while none of them failed.
+    pub fn failure_rate(rate: f32, window: usize, cool_down: Duration) -> Self {
+        let window = window.max(1);
+        Self::new(Trip::Rate { rate, window }, cool_down)
+    }
+
+    /// Let `probes` calls through while half-open, all of which have to
+    /// succeed to close the circuit, 1 by default
+    pub fn half_open_probes(self, probes: usize) -> Self {
+        CircuitBreaker {
+            probes: probes.max(1),
+            ..self
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The current state, an open circuit past its cool-down is half-open
+    pub fn state(&self) -> State {
+        match self.lock().circuit {
+            Circuit::Closed => State::Closed,
+            Circuit::Open { until } if cooling(until, Instant::now()) => State::Open,
+            Circuit::Open { .. } | Circuit::HalfOpen { .. } => State::HalfOpen,
+        }
+    }
+
+    /// Whether a call may go through now
+    ///
+    /// While half-open this admits one of the probes. A probe whose outcome
+    /// is never recorded frees its slot after the cool-down.
+    pub fn allow(&self) -> bool {
+        let now = Instant::now();
+        let mut inner = self.lock();
+        match &mut inner.circuit {
+            Circuit::Closed => true,
+            Circuit::Open { until } if cooling(*until, now) => false,
+            Circuit::Open { .. } => {
+                inner.circuit = Circuit::HalfOpen {
+                    admitted: 1,
+                    succeeded: 0,
+                    last: now,
+                };
+                true
+            }
+            Circuit::HalfOpen { admitted, last, .. } => {
+                if *admitted < self.probes {
+                    *admitted += 1;
+                } else if now.duration_since(*last) < self.cool_down {
+                    return false;
+                }
+                *last = now;
+                true
+            }
+        }
+    }
+
+    /// Record the outcome of a call
+    pub fn record<T: Tryable>(&self, output: &T) {
+        if output.negative() {
+            self.record_failure()
+        } else {
+            self.record_success()
+        }
+    }
+
+    /// Record a call that succeeded
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        match &mut inner.circuit {
+            Circuit::Closed => self.closed(&mut inner, false),
+            Circuit::HalfOpen { succeeded, .. } => {
+                *succeeded += 1;
+                if *succeeded >= self.probes {
+                    inner.circuit = Circuit::Closed;
+                }
+            }
+            // A call admitted before the circuit opened
+            Circuit::Open { .. } => {}
+        }
+    }
+
+    /// Record a call that failed
+    pub fn record_failure(&self) {
+        let mut inner = self.lock();
+        match inner.circuit {
+            Circuit::Closed => self.closed(&mut inner, true),
+            Circuit::HalfOpen { .. } => self.open(&mut inner),
+            Circuit::Open { .. } => {}
+        }
+    }
+
+    /// Record the outcome of a call while closed, tripping if need be
+    fn closed(&self, inner: &mut Inner, failed: bool) {
+        let trip = match self.trip {
+            Trip::Consecutive(failures) => {
+                inner.consecutive = if failed { inner.consecutive + 1 } else { 0 };
+                inner.consecutive >= failures
+            }
+            Trip::Rate { rate, window } => {
+                if inner.window.len() == window {
+                    inner.window.pop_front();
+                }
+                inner.window.push_back(failed);
+                let failures = inner.window.iter().filter(|failed| **failed).count();
+                // A rate of 0 still takes a failure to trip
+                inner.window.len() == window
+                    && failures > 0
+                    && failures as f32 >= rate * window as f32
+            }
+        };
+        if trip {
+            self.open(inner);
+        }
+    }
+
+    fn open(&self, inner: &mut Inner) {
+        inner.circuit = Circuit::Open {
+            until: Instant::now().checked_add(self.cool_down),
+        };
+        inner.consecutive = 0;
+        inner.window.clear();
+    }
+}
+
+/// Whether an open circuit is still cooling down at `now`
+fn cooling(until: Option<Instant>, now: Instant) -> bool {
+    until.is_none_or(|until| now < until)
+}
+
+/// How a retrier consults a circuit breaker or a throttle around each
+/// attempt
+///
+/// A pair consults both, so `(breaker, throttle)` fails fast while the
+/// circuit is open and sheds load while it's closed.
+pub trait Breaker<Out> {
+    /// `Err` with the output and the reason to stop with if the attempt may
+    /// not be made, after `attempts` attempts
+    fn admit(&self, attempts: usize) -> Result<(), (Out, Stop)>;
+    fn record(&self, output: &Out);
+}
+
+/// Make every attempt
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoBreaker;
+
+impl<Out> Breaker<Out> for NoBreaker {
+    fn admit(&self, _: usize) -> Result<(), (Out, Stop)> {
+        Ok(())
+    }
+    fn record(&self, _: &Out) {}
+}
+
+impl<Out, A, B> Breaker<Out> for (A, B)
+where
+    A: Breaker<Out>,
+    B: Breaker<Out>,
+{
+    fn admit(&self, attempts: usize) -> Result<(), (Out, Stop)> {
+        self.0.admit(attempts)?;
+        self.1.admit(attempts)
+    }
+    fn record(&self, output: &Out) {
+        self.0.record(output);
+        self.1.record(output);
+    }
+}
+
+impl<Out> Breaker<Out> for Arc<CircuitBreaker>
+where
+    Out: Tryable + FromCircuitOpen,
+{
+    fn admit(&self, attempts: usize) -> Result<(), (Out, Stop)> {
+        if self.allow() {
+            Ok(())
+        } else {
+            Err((Out::circuit_open(), Stop::CircuitOpen { attempts }))
+        }
+    }
+    fn record(&self, output: &Out) {
+        CircuitBreaker::record(self, output)
+    }
+}
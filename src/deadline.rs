@@ -0,0 +1,27 @@
+use core::time::Duration;
+use std::time::Instant;
+
+/// Bound on the total time a retrier spends, see `max_elapsed` and
+/// `deadline` on the retriers
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Deadline {
+    /// Measured from the start of the first attempt
+    Elapsed(Duration),
+    At(Instant),
+}
+
+impl Deadline {
+    /// Whether waiting `delay` before the next attempt would take the
+    /// retrier past the deadline
+    pub(crate) fn crossed_by(self, started: Instant, delay: Duration) -> bool {
+        let deadline = match self {
+            Deadline::Elapsed(limit) => started.checked_add(limit),
+            Deadline::At(at) => Some(at),
+        };
+        match (deadline, Instant::now().checked_add(delay)) {
+            (Some(deadline), Some(next)) => next >= deadline,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! Reporting why a retrier gave up.
+//!
+//! By default the retriers hand back the output of the last attempt. Opting
+//! into [`RetryError`] (`try_retry` on the oneshot traits,
+//! `.with_retry_error()` on the builder and async retriers) turns that into a
+//! `Result` that tells the different ways of giving up apart.
+
+use core::fmt;
+
+use crate::tryable::Tryable;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryError<E> {
+    /// Every attempt failed, `last` is the error of the final one
+    Exhausted { last: E, attempts: usize },
+    /// An attempt failed with an error that isn't worth retrying
+    Permanent(E),
+    /// The retry budget or the adaptive throttle refused the next attempt,
+    /// `last` is the error of the final one
+    Throttled { last: E, attempts: usize },
+    /// The retrier ran out of time
+    TimedOut,
+    /// The circuit breaker refused the next attempt, `last` is the error of
+    /// the final one, or the [`CircuitOpen`] error if none was made
+    CircuitOpen { last: E, attempts: usize },
+    /// The [`CancelToken`](crate::cancel::CancelToken) of the retrier was
+    /// cancelled
+    Cancelled,
+}
+
+impl<E> RetryError<E> {
+    /// The error of the last attempt, if one was made
+    pub fn into_inner(self) -> Option<E> {
+        match self {
+            RetryError::Exhausted { last, .. }
+            | RetryError::Throttled { last, .. }
+            | RetryError::CircuitOpen { last, .. }
+            | RetryError::Permanent(last) => Some(last),
+            RetryError::TimedOut | RetryError::Cancelled => None,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryError::Exhausted { last, attempts } => {
+                write!(f, "gave up after {attempts} attempts: {last}")
+            }
+            RetryError::Throttled { last, attempts } => {
+                write!(f, "retries throttled after {attempts} attempts: {last}")
+            }
+            RetryError::Permanent(e) => write!(f, "permanent error: {e}"),
+            RetryError::TimedOut => f.write_str("timed out"),
+            RetryError::CircuitOpen { last, attempts } => {
+                write!(f, "circuit open after {attempts} attempts: {last}")
+            }
+            RetryError::Cancelled => f.write_str("cancelled"),
+        }
+    }
+}
+
+impl<E> std::error::Error for RetryError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RetryError::Exhausted { last, .. }
+            | RetryError::Throttled { last, .. }
+            | RetryError::CircuitOpen { last, .. }
+            | RetryError::Permanent(last) => Some(last),
+            RetryError::TimedOut | RetryError::Cancelled => None,
+        }
+    }
+}
+
+/// An attempt of the async retrier took longer than its
+/// `.attempt_timeout(..)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AttemptTimedOut;
+
+impl fmt::Display for AttemptTimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("attempt timed out")
+    }
+}
+
+impl std::error::Error for AttemptTimedOut {}
+
+impl From<AttemptTimedOut> for std::io::Error {
+    fn from(e: AttemptTimedOut) -> Self {
+        std::io::Error::new(std::io::ErrorKind::TimedOut, e)
+    }
+}
+
+/// The output of an attempt cut short by a timeout
+pub trait FromTimeout {
+    fn timed_out() -> Self;
+}
+
+impl<T, E: From<AttemptTimedOut>> FromTimeout for Result<T, E> {
+    fn timed_out() -> Self {
+        Err(AttemptTimedOut.into())
+    }
+}
+
+impl<T> FromTimeout for Option<T> {
+    fn timed_out() -> Self {
+        None
+    }
+}
+
+/// An attempt refused by an open
+/// [`CircuitBreaker`](crate::circuit::CircuitBreaker)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CircuitOpen;
+
+impl fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("circuit open")
+    }
+}
+
+impl std::error::Error for CircuitOpen {}
+
+impl From<CircuitOpen> for std::io::Error {
+    fn from(e: CircuitOpen) -> Self {
+        std::io::Error::new(std::io::ErrorKind::ConnectionRefused, e)
+    }
+}
+
+/// The output of an attempt refused by an open circuit
+pub trait FromCircuitOpen {
+    fn circuit_open() -> Self;
+}
+
+impl<T, E: From<CircuitOpen>> FromCircuitOpen for Result<T, E> {
+    fn circuit_open() -> Self {
+        Err(CircuitOpen.into())
+    }
+}
+
+impl<T> FromCircuitOpen for Option<T> {
+    fn circuit_open() -> Self {
+        None
+    }
+}
+
+/// A request rejected locally by an
+/// [`AdaptiveThrottle`](crate::throttle::AdaptiveThrottle)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Throttled;
+
+impl fmt::Display for Throttled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("throttled")
+    }
+}
+
+impl std::error::Error for Throttled {}
+
+impl From<Throttled> for std::io::Error {
+    fn from(e: Throttled) -> Self {
+        std::io::Error::new(std::io::ErrorKind::WouldBlock, e)
+    }
+}
+
+/// The output of an attempt the throttle rejected
+pub trait FromThrottled {
+    fn throttled() -> Self;
+}
+
+impl<T, E: From<Throttled>> FromThrottled for Result<T, E> {
+    fn throttled() -> Self {
+        Err(Throttled.into())
+    }
+}
+
+impl<T> FromThrottled for Option<T> {
+    fn throttled() -> Self {
+        None
+    }
+}
+
+/// Why a retrier stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Success,
+    Permanent,
+    Exhausted {
+        attempts: usize,
+    },
+    /// The retry budget or the adaptive throttle refused the next attempt
+    Throttled {
+        attempts: usize,
+    },
+    /// The next attempt would start past the deadline
+    TimedOut,
+    /// The circuit breaker refused the next attempt
+    CircuitOpen {
+        attempts: usize,
+    },
+    /// The token of the retrier was cancelled
+    Cancelled,
+}
+
+/// What a retrier resolves to once it stops
+pub trait Outcome<O> {
+    type Output;
+    fn report(output: O, stop: Stop) -> Self::Output;
+}
+
+/// Hand back the output of the last attempt as is
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LastOutput;
+
+impl<O> Outcome<O> for LastOutput {
+    type Output = O;
+    fn report(output: O, _: Stop) -> O {
+        output
+    }
+}
+
+/// Resolve to a `Result` with a [`RetryError`] describing the failure
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WithRetryError;
+
+impl<O: Tryable> Outcome<O> for WithRetryError {
+    type Output = Result<O::Ok, RetryError<O::Error>>;
+    fn report(output: O, stop: Stop) -> Self::Output {
+        output.into_result().map_err(|last| match stop {
+            Stop::Exhausted { attempts } => RetryError::Exhausted { last, attempts },
+            Stop::Throttled { attempts } => RetryError::Throttled { last, attempts },
+            Stop::TimedOut => RetryError::TimedOut,
+            Stop::Cancelled => RetryError::Cancelled,
+            Stop::CircuitOpen { attempts } => RetryError::CircuitOpen { last, attempts },
+            Stop::Success | Stop::Permanent => RetryError::Permanent(last),
+        })
+    }
+}
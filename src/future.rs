@@ -1,3 +1,5 @@
+pub mod async_fn;
+pub mod hedge;
 pub mod repeat;
 pub mod retry;
-
+pub mod sleep;
@@ -0,0 +1,280 @@
+//! Title: Async closures
+//!
+//! The traits in [`retry`](super::retry) and [`repeat`](super::repeat) store
+//! the future next to the function, so it can't borrow from the function or
+//! its arguments. These ones take any `AsyncFnMut`, so an attempt can hold on
+//! to captured state or to its arguments across await points.
+//!
+//! How to use:
+//! ```rust
+//! use retry::future::async_fn::*;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let mut calls = 0;
+//! let flaky = async |name: &str| {
+//!     calls += 1;
+//!     tokio::task::yield_now().await;
+//!     if calls < 3 { Err(calls) } else { Ok(format!("{name} after {calls}")) }
+//! };
+//! assert_eq!(flaky.retry::<3>(("hello",)).await.as_deref(), Ok("hello after 3"));
+//! # }
+//! ```
+//!
+//! Like the traits in [`tuple`](crate::tuple), these take the arguments packed
+//! in a tuple, or any other [`ArgSource`]. Methods borrowing their receiver
+//! mutably are retried with [`retry_with`], which reborrows it for each
+//! attempt and can wait between attempts.
+
+use core::future::Future;
+
+use super::sleep::{Sleeper, ThreadSleeper};
+use crate::attempt::Attempts;
+use crate::backoff::{Backoff, NoBackoff};
+use crate::predicate::{Always, RetryIf};
+use crate::tryable::Tryable;
+use crate::tuple::{stop, ArgSource};
+
+/// An `AsyncFnMut` called with its arguments packed in a tuple
+trait AsyncTupleCall<Args> {
+    type Output;
+    fn call_tuple(&mut self, args: Args) -> impl Future<Output = Self::Output>;
+}
+
+macro_rules! impl_async_tuple_call {
+    ($($item: ident),*) => {
+        #[allow(non_snake_case)]
+        impl<F, O, $($item),*> AsyncTupleCall<($($item,)*)> for F
+        where
+            F: AsyncFnMut($($item),*) -> O,
+        {
+            type Output = O;
+            fn call_tuple(&mut self, ($($item,)*): ($($item,)*)) -> impl Future<Output = O> {
+                self($($item),*)
+            }
+        }
+    };
+}
+
+impl_async_tuple_call!();
+impl_async_tuple_call!(A1);
+impl_async_tuple_call!(A1, A2);
+impl_async_tuple_call!(A1, A2, A3);
+impl_async_tuple_call!(A1, A2, A3, A4);
+impl_async_tuple_call!(A1, A2, A3, A4, A5);
+impl_async_tuple_call!(A1, A2, A3, A4, A5, A6);
+impl_async_tuple_call!(A1, A2, A3, A4, A5, A6, A7);
+impl_async_tuple_call!(A1, A2, A3, A4, A5, A6, A7, A8);
+impl_async_tuple_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_async_tuple_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_async_tuple_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_async_tuple_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
+
+/// Await `attempt` with the attempt number until it succeeds, fails with an
+/// error `predicate` won't retry or runs out of attempts, sleeping for the
+/// delays of `backoff` in between
+async fn retry<Output, B, S, P>(
+    limit: Attempts,
+    mut backoff: B,
+    sleeper: S,
+    mut predicate: P,
+    mut attempt: impl AsyncFnMut(usize) -> Output,
+) -> Output
+where
+    Output: Tryable,
+    B: Backoff,
+    S: Sleeper,
+    P: RetryIf<Output::Error>,
+{
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let result = attempt(attempts).await;
+        if stop(&result, attempts, limit).is_some() {
+            return result;
+        }
+        let delay = match result.error() {
+            Some(error) if predicate.should_retry(error) => backoff.next().unwrap_or_default(),
+            _ => return result,
+        };
+        if !delay.is_zero() {
+            sleeper.sleep(delay).await;
+        }
+    }
+}
+
+pub trait AsyncFnRetry<Args, Output>: Sized {
+    /// Retry with a count read at runtime, see [`Attempts`]
+    fn retry_n(self, attempts: impl Into<Attempts>, args: Args) -> impl Future<Output = Output>;
+
+    fn retry<const N: usize>(self, args: Args) -> impl Future<Output = Output> {
+        self.retry_n(N, args)
+    }
+}
+
+impl<F, Args, Output> AsyncFnRetry<Args, Output> for F
+where
+    Args: ArgSource,
+    F: AsyncTupleCall<Args::Args, Output = Output>,
+    Output: Tryable,
+{
+    async fn retry_n(mut self, attempts: impl Into<Attempts>, mut args: Args) -> Output {
+        let attempt = async |n| self.call_tuple(args.args(n)).await;
+        retry(attempts.into(), NoBackoff, ThreadSleeper, Always, attempt).await
+    }
+}
+
+pub trait AsyncFnRepeat<Args, Output>: Sized {
+    /// Repeat `times` times, read at runtime, and at least once
+    fn repeat_n(self, times: usize, args: Args) -> impl Future<Output = Output>;
+
+    fn repeat<const N: usize>(self, args: Args) -> impl Future<Output = Output> {
+        self.repeat_n(N, args)
+    }
+}
+
+impl<F, Args, Output> AsyncFnRepeat<Args, Output> for F
+where
+    Args: ArgSource,
+    F: AsyncTupleCall<Args::Args, Output = Output>,
+{
+    async fn repeat_n(mut self, times: usize, mut args: Args) -> Output {
+        for n in 1..times {
+            self.call_tuple(args.args(n)).await;
+        }
+        self.call_tuple(args.args(times.max(1))).await
+    }
+}
+
+/// Retry `f` with a count read at runtime, see [`Attempts`], handing it a
+/// fresh reborrow of `receiver` for each attempt
+///
+/// This is how to retry a method taking `&mut self`, like a client holding
+/// a connection:
+/// ```rust
+/// use core::time::Duration;
+/// use retry::backoff::Constant;
+/// use retry::future::async_fn::retry_with;
+///
+/// struct Client {
+///     requests: usize,
+/// }
+///
+/// impl Client {
+///     async fn fetch(&mut self, path: &str) -> Result<String, String> {
+///         self.requests += 1;
+///         if self.requests < 3 {
+///             Err(format!("{path} failed"))
+///         } else {
+///             Ok(format!("{path} after {} requests", self.requests))
+///         }
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let mut client = Client { requests: 0 };
+/// let path = String::from("/status");
+/// let page = retry_with(&mut client, 3, async |c| c.fetch(&path).await)
+///     .backoff(Constant::new(Duration::from_millis(10)))
+///     .sleeper(tokio::time::sleep)
+///     .run()
+///     .await;
+/// assert_eq!(page.as_deref(), Ok("/status after 3 requests"));
+/// assert_eq!(client.requests, 3);
+/// # }
+/// ```
+pub fn retry_with<C, F, Output>(
+    receiver: &mut C,
+    attempts: impl Into<Attempts>,
+    f: F,
+) -> RetryWith<'_, C, F>
+where
+    C: ?Sized,
+    F: AsyncFnMut(&mut C) -> Output,
+    Output: Tryable,
+{
+    RetryWith {
+        receiver,
+        limit: attempts.into(),
+        f,
+        backoff: NoBackoff,
+        sleeper: ThreadSleeper,
+        predicate: Always,
+    }
+}
+
+/// The retries of a function reborrowing a receiver, see [`retry_with`]
+///
+/// Unlike [`Retrier`](super::retry::Retrier) it has no timeouts, hooks,
+/// budget or circuit breaker.
+pub struct RetryWith<'a, C: ?Sized, F, B = NoBackoff, S = ThreadSleeper, P = Always> {
+    receiver: &'a mut C,
+    limit: Attempts,
+    f: F,
+    backoff: B,
+    sleeper: S,
+    predicate: P,
+}
+
+impl<'a, C: ?Sized, F, B, S, P> RetryWith<'a, C, F, B, S, P> {
+    /// Wait between attempts according to `backoff`
+    pub fn backoff<B2: Backoff>(self, backoff: B2) -> RetryWith<'a, C, F, B2, S, P> {
+        RetryWith {
+            receiver: self.receiver,
+            limit: self.limit,
+            f: self.f,
+            backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+        }
+    }
+
+    /// Wait between attempts using the timer of a specific runtime
+    pub fn sleeper<S2: Sleeper>(self, sleeper: S2) -> RetryWith<'a, C, F, B, S2, P> {
+        RetryWith {
+            receiver: self.receiver,
+            limit: self.limit,
+            f: self.f,
+            backoff: self.backoff,
+            sleeper,
+            predicate: self.predicate,
+        }
+    }
+
+    /// Only retry errors for which `predicate` returns `true`, any other
+    /// error is returned straight away
+    pub fn retry_if<P2>(self, predicate: P2) -> RetryWith<'a, C, F, B, S, P2> {
+        RetryWith {
+            receiver: self.receiver,
+            limit: self.limit,
+            f: self.f,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate,
+        }
+    }
+
+    /// Do the retries
+    pub async fn run<Output>(self) -> Output
+    where
+        F: AsyncFnMut(&mut C) -> Output,
+        Output: Tryable,
+        B: Backoff,
+        S: Sleeper,
+        P: RetryIf<Output::Error>,
+    {
+        let RetryWith {
+            receiver,
+            limit,
+            mut f,
+            backoff,
+            sleeper,
+            predicate,
+        } = self;
+        retry(limit, backoff, sleeper, predicate, async |_| {
+            f(receiver).await
+        })
+        .await
+    }
+}
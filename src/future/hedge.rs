@@ -0,0 +1,178 @@
+//! Title: Hedger
+//!
+//! Start another attempt whenever the ones in flight take longer than the
+//! stagger, up to a maximum, and resolve to the first one that succeeds along
+//! with its number, counted from 1. The other attempts are dropped. A failed
+//! attempt makes room for the next one straight away, and once every attempt
+//! failed the hedger resolves to the last failure.
+//!
+//! How to use:
+//! ```rust
+//! use core::time::Duration;
+//! use retry::tuple::*;
+//!
+//! async fn read(key: &'static str) -> Result<String, ()> {
+//!     Ok(key.to_uppercase())
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let (value, attempt) = read
+//!     .hedge::<3>(Duration::from_millis(20), ("key",))
+//!     .sleeper(tokio::time::sleep)
+//!     .await;
+//! assert_eq!(value.as_deref(), Ok("KEY"));
+//! assert_eq!(attempt, 1);
+//! # }
+//! ```
+//!
+//! The [`AsyncHedge`](crate::tuple::AsyncHedge) trait takes the arguments
+//! packed in a tuple, see [`tuple`](crate::tuple).
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::Poll;
+use core::time::Duration;
+
+use super::sleep::{Sleeper, ThreadSleeper};
+use crate::tryable::Tryable;
+use crate::tuple::{ArgSource, TupleCall};
+
+#[pin_project::pin_project]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Hedger<F, Args, Fut: Future, S: Sleeper = ThreadSleeper> {
+    attempts: usize,
+    started: usize,
+    stagger: Duration,
+    f: F,
+    args: Args,
+    sleeper: S,
+    /// The attempts in flight with their number
+    in_flight: Vec<(usize, Pin<Box<Fut>>)>,
+    #[pin]
+    timer: Option<S::Sleep>,
+    failed: Option<(Fut::Output, usize)>,
+}
+
+impl<F, Args: core::fmt::Debug, Fut: Future, S: Sleeper> core::fmt::Debug
+    for Hedger<F, Args, Fut, S>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Hedger")
+            .field("args", &self.args)
+            .field("attempts", &self.attempts)
+            .field("started", &self.started)
+            .field("stagger", &self.stagger)
+            .finish()
+    }
+}
+
+impl<F, Args, Fut: Future> Hedger<F, Args, Fut> {
+    pub(crate) fn new(attempts: usize, stagger: Duration, f: F, args: Args) -> Self {
+        Hedger {
+            attempts: attempts.max(1),
+            started: 0,
+            stagger,
+            f,
+            args,
+            sleeper: ThreadSleeper,
+            in_flight: Vec::new(),
+            timer: None,
+            failed: None,
+        }
+    }
+}
+
+impl<F, Args, Fut: Future, S: Sleeper> Hedger<F, Args, Fut, S> {
+    /// Wait for the stagger using the timer of a specific runtime
+    ///
+    /// The stagger of the attempts in flight starts over on the new timer.
+    pub fn sleeper<S2: Sleeper>(self, sleeper: S2) -> Hedger<F, Args, Fut, S2> {
+        Hedger {
+            attempts: self.attempts,
+            started: self.started,
+            stagger: self.stagger,
+            f: self.f,
+            args: self.args,
+            sleeper,
+            in_flight: self.in_flight,
+            timer: None,
+            failed: self.failed,
+        }
+    }
+}
+
+impl<F, Args, Fut, S> Future for Hedger<F, Args, Fut, S>
+where
+    F: TupleCall<Args::Args, Output = Fut>,
+    Args: ArgSource,
+    Fut: Future,
+    Fut::Output: Tryable,
+    S: Sleeper,
+{
+    type Output = (Fut::Output, usize);
+    fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            if this.in_flight.is_empty() {
+                if *this.started == *this.attempts {
+                    let failed = this.failed.take();
+                    return Poll::Ready(failed.expect("polled after completion"));
+                }
+                start(this.f, this.args, this.started, this.in_flight);
+                this.timer.set(None);
+            }
+            if this.timer.is_none() && *this.started < *this.attempts {
+                this.timer.set(Some(this.sleeper.sleep(*this.stagger)));
+            }
+
+            let mut i = 0;
+            while let Some((number, attempt)) = this.in_flight.get_mut(i) {
+                let Poll::Ready(v) = attempt.as_mut().poll(cx) else {
+                    i += 1;
+                    continue;
+                };
+                let number = *number;
+                this.in_flight.swap_remove(i);
+                if !v.negative() {
+                    // Drop the losers
+                    this.in_flight.clear();
+                    this.timer.set(None);
+                    return Poll::Ready((v, number));
+                }
+                *this.failed = Some((v, number));
+                // A failed attempt makes room for the next one straight away
+                if *this.started < *this.attempts {
+                    start(this.f, this.args, this.started, this.in_flight);
+                    let more = *this.started < *this.attempts;
+                    this.timer
+                        .set(more.then(|| this.sleeper.sleep(*this.stagger)));
+                }
+            }
+
+            match this.timer.as_mut().as_pin_mut().map(|timer| timer.poll(cx)) {
+                Some(Poll::Ready(_)) => {
+                    this.timer.set(None);
+                    start(this.f, this.args, this.started, this.in_flight);
+                }
+                _ if this.in_flight.is_empty() => {}
+                _ => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Start the next attempt
+fn start<F, Args, Fut>(
+    f: &mut F,
+    args: &mut Args,
+    started: &mut usize,
+    in_flight: &mut Vec<(usize, Pin<Box<Fut>>)>,
+) where
+    F: TupleCall<Args::Args, Output = Fut>,
+    Args: ArgSource,
+{
+    *started += 1;
+    let args = args.args(*started);
+    in_flight.push((*started, Box::pin(f.call_tuple(args))));
+}
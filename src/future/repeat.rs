@@ -2,17 +2,20 @@
 //!
 //! How to use:
 //! ```rust
-//! use retry::future::*;
+//! use retry::future::repeat::*;
 //! pub async fn myfunc() {
 //!     println!("Hello, world!");
 //!     // Other stuff
 //! }
+//! # async fn run() {
 //! myfunc.repeat::<3>().await;
+//! # }
 //! ```
 
 use core::future::Future;
-use core::pin::Pin;
-use core::task::Poll;
+use core::task::{ready, Poll};
+
+use crate::tuple::{ArgSource, TupleCall};
 
 #[pin_project::pin_project]
 #[non_exhaustive]
@@ -22,6 +25,7 @@ pub struct Repeater<F, Args, Fut> {
     #[pin]
     state: RepeaterStates<Fut>,
     repeat: usize,
+    calls: usize,
     args: Args,
 }
 
@@ -42,44 +46,45 @@ pub enum RepeaterStates<F> {
     Ready(#[pin] F),
 }
 
-impl<F, Fut, Out> Future for Repeater<F, (), Fut>
+impl<F, Args, Fut> Repeater<F, Args, Fut> {
+    pub(crate) fn new(times: usize, f: F, args: Args) -> Self {
+        Repeater {
+            f,
+            state: RepeaterStates::Pending,
+            repeat: times,
+            calls: 0,
+            args,
+        }
+    }
+}
+
+impl<F, Args, Fut> Future for Repeater<F, Args, Fut>
 where
-    F: Fn() -> Fut,
-    Fut: Future<Output = Out>,
+    F: TupleCall<Args::Args, Output = Fut>,
+    Args: ArgSource,
+    Fut: Future,
 {
-    type Output = Out;
+    type Output = Fut::Output;
     fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
         let mut this = self.project();
-        let mut tries = *this.repeat;
-        match this.state.as_mut().project() {
-            RepeaterState::Pending => {
-                // Create the future from the function
-                let fut = (this.f)();
-                this.state.set(RepeaterStates::Ready(fut));
-                cx.waker().wake_by_ref();
-                Poll::Pending
-            }
-
-            RepeaterState::Ready(ref mut fut) => {
-                match Pin::new(fut).poll(cx) {
-                    // If it's already ready then decrement the repeat counter for 1
-                    Poll::Ready(v) => {
-                        tries -= 1;
-                        if tries == 0 {
-                            Poll::Ready(v)
-                        } else {
-                            // So we need to retry more so create a new future
-                            let fut = (this.f)();
-                            this.state.set(RepeaterStates::Ready(fut));
-                            *this.repeat = tries;
-                            cx.waker().wake_by_ref();
-                            Poll::Pending
-                        }
-                    }
-                    Poll::Pending => {
-                        cx.waker().wake_by_ref();
-                        Poll::Pending
+        loop {
+            match this.state.as_mut().project() {
+                RepeaterState::Pending => {
+                    // Create the future from the function and poll it straight away
+                    *this.calls += 1;
+                    let args = this.args.args(*this.calls);
+                    let fut = this.f.call_tuple(args);
+                    this.state.set(RepeaterStates::Ready(fut));
+                }
+                RepeaterState::Ready(fut) => {
+                    // Only the inner future registers a wakeup, so a pending
+                    // attempt doesn't spin the executor
+                    let v = ready!(fut.poll(cx));
+                    if *this.repeat <= 1 {
+                        return Poll::Ready(v);
                     }
+                    *this.repeat -= 1;
+                    this.state.set(RepeaterStates::Pending);
                 }
             }
         }
@@ -87,91 +92,43 @@ where
 }
 
 pub trait AsyncRepeat0<Fut>: Sized {
-    fn repeat<const N: usize>(self) -> Repeater<Self, (), Fut>;
+    /// Repeat `times` times, read at runtime, and at least once
+    fn repeat_n(self, times: usize) -> Repeater<Self, (), Fut> {
+        Repeater::new(times, self, ())
+    }
+
+    fn repeat<const N: usize>(self) -> Repeater<Self, (), Fut> {
+        self.repeat_n(N)
+    }
 }
 
 impl<F, Fut, Out> AsyncRepeat0<Fut> for F
 where
-    F: Fn() -> Fut,
+    F: FnMut() -> Fut,
     Fut: Future<Output = Out>,
 {
-    fn repeat<const N: usize>(self) -> Repeater<Self, (), Fut> {
-        Repeater {
-            f: self,
-            state: RepeaterStates::Pending,
-            repeat: N,
-            args: (),
-        }
-    }
 }
 
 macro_rules! impl_gen_async_repeat {
     ($name:ident, $($item: ident),*) => {
         #[allow(non_snake_case, clippy::too_many_arguments)]
         pub trait $name<Fut, $($item),*>: Sized {
+            /// Repeat `times` times, read at runtime, and at least once
+            fn repeat_n(self, times: usize, $($item: $item),*) -> Repeater<Self, ($($item),*,), Fut> {
+                Repeater::new(times, self, ($($item),*,))
+            }
+
             fn repeat<const N: usize>(self, $($item: $item),*) -> Repeater<Self, ($($item),*,), Fut> {
-                Repeater {
-                    f: self,
-                    state: RepeaterStates::Pending,
-                    repeat: N,
-                    args: ($($item),*,),
-                }
+                self.repeat_n(N, $($item),*)
             }
         }
 
         #[allow(non_snake_case)]
         impl<F, Fut, Out, $($item),*> $name<Fut, $($item),*> for F
         where
-            F: Fn($($item),*) -> Fut,
+            F: FnMut($($item),*) -> Fut,
             Fut: Future<Output = Out>,
         { }
-
-        #[allow(non_snake_case)]
-        impl<F, Fut, Out, $($item: Clone),*> Future for Repeater<F, ($($item),*,), Fut>
-            where
-                F: Fn($($item),*) -> Fut,
-                Fut: Future<Output = Out>,
-        {
-            type Output = Out;
-            fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-                let mut this = self.project();
-                let mut tries = *this.repeat;
-                match this.state.as_mut().project() {
-                    RepeaterState::Pending => {
-                        // Create the future from the function
-                        let ( $($item),*, ) = this.args.clone();
-                        let fut = (this.f)($($item),*,);
-                        this.state.set(RepeaterStates::Ready(fut));
-                        cx.waker().wake_by_ref();
-                        Poll::Pending
-                    }
-
-                    RepeaterState::Ready(ref mut fut) => {
-                        match Pin::new(fut).poll(cx) {
-                            // If it's already ready then decrement the repeat counter for 1
-                            Poll::Ready(v) => {
-                                tries -= 1;
-                                if tries == 0 {
-                                    Poll::Ready(v)
-                                } else {
-                                    let ( $($item),*, ) = this.args.clone();
-                                    let fut = (this.f)($($item),*,);
-                                    this.state.set(RepeaterStates::Ready(fut));
-                                    *this.repeat = tries;
-                                    cx.waker().wake_by_ref();
-                                    Poll::Pending
-                                }
-                            }
-                            Poll::Pending => {
-                                cx.waker().wake_by_ref();
-                                Poll::Pending
-                            }
-                        }
-                    }
-                }
-            }
-
-         }
     };
 }
 
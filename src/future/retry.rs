@@ -1,59 +1,689 @@
 use core::future::Future;
-use core::pin::Pin;
-use core::task::Poll;
+use core::task::{ready, Poll};
+use core::time::Duration;
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::sleep::{Sleeper, ThreadSleeper};
+use crate::attempt::{Attempt, Attempts, WithAttempt};
+use crate::backoff::{Backoff, NoBackoff};
+use crate::budget::RetryBudget;
+use crate::call::Call;
+use crate::cancel::{cancelled, CancelToken};
+use crate::circuit::{Breaker, CircuitBreaker, NoBreaker};
+use crate::deadline::Deadline;
+use crate::error::{
+    FromCircuitOpen, FromThrottled, FromTimeout, LastOutput, Outcome, Stop, WithRetryError,
+};
+use crate::hint::{Hinted, NoHint, ReadHint};
+use crate::hooks::{AsyncHook, AsyncNotify, HookFuture, Hooks};
+use crate::predicate::{Always, RetryIf};
+use crate::throttle::{AdaptiveThrottle, WithThrottle};
+use crate::tryable::Tryable;
+use crate::tuple::ArgSource;
 
 #[pin_project::pin_project(project = RetryStates)]
-pub enum RetryState<F> {
+pub enum RetryState<F, S> {
     Pending,
-    Ready(#[pin] F),
+    /// The attempt in flight and its timeout, if any
+    Ready(#[pin] F, #[pin] Option<S>),
+    Sleeping(#[pin] S),
+    Notifying(HookFuture),
+}
+
+/// What the retrier has done so far
+struct Progress<Out> {
+    attempts: usize,
+    started: Option<Instant>,
+    /// Output of the last failed attempt
+    previous: Option<Out>,
+    /// Final output, held while an async hook runs
+    finished: Option<(Out, Stop)>,
+    /// Delay to wait once an async `on_retry` hook is done
+    delay: Duration,
 }
 
+/// Time allowed per attempt and the output of one that takes longer
+type Timeout<Out> = (Duration, fn() -> Out);
+
 #[pin_project::pin_project]
-pub struct Retrier<F, Args, Fut> {
+pub struct Retrier<
+    F,
+    Args,
+    Fut: Future,
+    B = NoBackoff,
+    S: Sleeper = ThreadSleeper,
+    P = Always,
+    M = LastOutput,
+    H = Hooks,
+    C = NoBreaker,
+    W = NoHint,
+> {
     retry: usize,
+    progress: Progress<Fut::Output>,
+    timeout: Option<Timeout<Fut::Output>>,
+    deadline: Option<Deadline>,
+    budget: Option<Arc<RetryBudget>>,
+    cancel: Option<CancelToken>,
     f: F,
     #[pin]
-    state: RetryState<Fut>,
+    state: RetryState<Fut, S::Sleep>,
     args: Args,
+    backoff: B,
+    sleeper: S,
+    predicate: P,
+    hint: W,
+    outcome: M,
+    hooks: H,
+    breaker: C,
+}
+
+impl<F, Args, Fut: Future> Retrier<F, Args, Fut> {
+    pub(crate) fn new(attempts: Attempts, f: F, args: Args) -> Self {
+        Retrier {
+            retry: attempts.retries,
+            progress: Progress {
+                attempts: 0,
+                started: None,
+                previous: None,
+                finished: None,
+                delay: Duration::ZERO,
+            },
+            timeout: None,
+            deadline: None,
+            budget: None,
+            cancel: None,
+            f,
+            state: RetryState::Pending,
+            args,
+            backoff: NoBackoff,
+            sleeper: ThreadSleeper,
+            predicate: Always,
+            hint: NoHint,
+            outcome: LastOutput,
+            hooks: Hooks::default(),
+            breaker: NoBreaker,
+        }
+    }
+}
+
+impl<F, Args, Fut: Future, B, S: Sleeper, P, M, H, C, W>
+    Retrier<F, Args, Fut, B, S, P, M, H, C, W>
+{
+    /// Wait between attempts according to `backoff`
+    pub fn backoff<B2: Backoff>(self, backoff: B2) -> Retrier<F, Args, Fut, B2, S, P, M, H, C, W> {
+        Retrier {
+            retry: self.retry,
+            progress: self.progress,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            f: self.f,
+            state: self.state,
+            args: self.args,
+            backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Wait between attempts using the timer of a specific runtime
+    pub fn sleeper<S2: Sleeper>(self, sleeper: S2) -> Retrier<F, Args, Fut, B, S2, P, M, H, C, W> {
+        let state = match self.state {
+            RetryState::Ready(fut, timer) => {
+                // The timeout of the attempt in flight starts over on the new timer
+                let timer = timer
+                    .and(self.timeout)
+                    .map(|(limit, _)| sleeper.sleep(limit));
+                RetryState::Ready(fut, timer)
+            }
+            RetryState::Notifying(fut) => RetryState::Notifying(fut),
+            RetryState::Pending | RetryState::Sleeping(_) => RetryState::Pending,
+        };
+        Retrier {
+            retry: self.retry,
+            progress: self.progress,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            f: self.f,
+            state,
+            args: self.args,
+            backoff: self.backoff,
+            sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Only retry errors for which `predicate` returns `true`, any other
+    /// error is returned straight away
+    pub fn retry_if<P2>(self, predicate: P2) -> Retrier<F, Args, Fut, B, S, P2, M, H, C, W> {
+        Retrier {
+            retry: self.retry,
+            progress: self.progress,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            f: self.f,
+            state: self.state,
+            args: self.args,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Sleep for the [`RetryHint`](crate::hint::RetryHint) of the errors
+    /// that have one, e.g. a parsed
+    /// [`RetryAfter`](crate::hint::RetryAfter) header, rather than for the
+    /// next delay of the backoff
+    ///
+    /// The predicate of [`retry_if`](Self::retry_if) still decides which
+    /// errors are retried, whichever is set first.
+    pub fn retry_hint(self) -> Retrier<F, Args, Fut, B, S, P, M, H, C, Hinted> {
+        self.hint(Hinted)
+    }
+
+    /// Resolve to a `Result` with a [`RetryError`](crate::RetryError) instead
+    /// of the output of the last attempt
+    pub fn with_retry_error(self) -> Retrier<F, Args, Fut, B, S, P, WithRetryError, H, C, W> {
+        self.outcome(WithRetryError)
+    }
+
+    pub(crate) fn outcome<M2>(self, outcome: M2) -> Retrier<F, Args, Fut, B, S, P, M2, H, C, W> {
+        Retrier {
+            retry: self.retry,
+            progress: self.progress,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            f: self.f,
+            state: self.state,
+            args: self.args,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome,
+            hooks: self.hooks,
+            breaker: self.breaker,
+        }
+    }
+
+    /// Give up on an attempt that takes longer than `timeout`, counting it as
+    /// a failed attempt
+    ///
+    /// The attempt fails with an error converted from
+    /// [`AttemptTimedOut`](crate::error::AttemptTimedOut), or with `None` for
+    /// functions returning an `Option`. The timeout runs on the
+    /// [`sleeper`](Self::sleeper) of the retrier.
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use retry::future::retry::*;
+    ///
+    /// async fn fetch() -> std::io::Result<u32> {
+    ///     core::future::pending().await
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let res = fetch
+    ///     .retry::<2>()
+    ///     .sleeper(tokio::time::sleep)
+    ///     .attempt_timeout(Duration::from_millis(10))
+    ///     .await;
+    /// assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::TimedOut);
+    /// # }
+    /// ```
+    pub fn attempt_timeout(self, timeout: Duration) -> Self
+    where
+        Fut::Output: FromTimeout,
+    {
+        Retrier {
+            timeout: Some((timeout, Fut::Output::timed_out)),
+            ..self
+        }
+    }
+
+    /// Stop retrying once the next attempt would start more than `limit`
+    /// after the first one, resolving to
+    /// [`RetryError::TimedOut`](crate::RetryError::TimedOut) with
+    /// [`with_retry_error`](Self::with_retry_error)
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use retry::backoff::Exponential;
+    /// use retry::future::retry::*;
+    /// use retry::RetryError;
+    ///
+    /// async fn fetch() -> Result<(), String> {
+    ///     Err("unavailable".into())
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let res = fetch
+    ///     .retry::<5>()
+    ///     .backoff(Exponential::new(Duration::from_millis(10)))
+    ///     .sleeper(tokio::time::sleep)
+    ///     .max_elapsed(Duration::from_millis(50))
+    ///     .with_retry_error()
+    ///     .await;
+    /// assert_eq!(res, Err(RetryError::TimedOut));
+    /// # }
+    /// ```
+    pub fn max_elapsed(self, limit: Duration) -> Self {
+        Retrier {
+            deadline: Some(Deadline::Elapsed(limit)),
+            ..self
+        }
+    }
+
+    /// Stop retrying once the next attempt would start after `deadline`, see
+    /// [`max_elapsed`](Self::max_elapsed)
+    pub fn deadline(self, deadline: Instant) -> Self {
+        Retrier {
+            deadline: Some(Deadline::At(deadline)),
+            ..self
+        }
+    }
+
+    /// Only retry while `budget` holds tokens, depositing into it on success
+    ///
+    /// A refused retry ends the retrier with
+    /// [`RetryError::Throttled`](crate::RetryError::Throttled), see
+    /// [`RetryBudget`].
+    pub fn budget(self, budget: impl Into<Arc<RetryBudget>>) -> Self {
+        Retrier {
+            budget: Some(budget.into()),
+            ..self
+        }
+    }
+
+    /// Stop retrying once `token` is cancelled
+    ///
+    /// The token is checked before every sleep and every retry, a sleep
+    /// already under way runs to its end. The retrier resolves to the output
+    /// of the last attempt, and to
+    /// [`RetryError::Cancelled`](crate::RetryError::Cancelled) with
+    /// [`with_retry_error`](Self::with_retry_error), see [`CancelToken`].
+    pub fn cancel_on(self, token: CancelToken) -> Self {
+        Retrier {
+            cancel: Some(token),
+            ..self
+        }
+    }
+
+    /// Fail fast while `breaker` is open, recording the outcome of every
+    /// attempt in it
+    ///
+    /// A refused attempt ends the retrier with the output of the last
+    /// attempt, or [`FromCircuitOpen::circuit_open`] if none was made, and
+    /// [`RetryError::CircuitOpen`](crate::RetryError::CircuitOpen).
+    pub fn circuit_breaker(
+        self,
+        breaker: impl Into<Arc<CircuitBreaker>>,
+    ) -> Retrier<F, Args, Fut, B, S, P, M, H, Arc<CircuitBreaker>, W>
+    where
+        Fut::Output: Tryable + FromCircuitOpen,
+    {
+        let breaker = breaker.into();
+        self.breaker(|_| breaker)
+    }
+
+    /// Let `throttle` reject attempts while the backend is overloaded,
+    /// recording the outcome of every attempt in it
+    ///
+    /// Unlike a circuit breaker it rejects at random, more often the fewer
+    /// requests the backend accepted lately. A rejected attempt ends the
+    /// retrier with the output of the last attempt, or
+    /// [`FromThrottled::throttled`] if none was made, and
+    /// [`RetryError::Throttled`](crate::RetryError::Throttled).
+    pub fn throttle(
+        self,
+        throttle: impl Into<Arc<AdaptiveThrottle>>,
+    ) -> Retrier<F, Args, Fut, B, S, P, M, H, WithThrottle<C>, W>
+    where
+        Fut::Output: Tryable + FromThrottled,
+    {
+        let throttle = throttle.into();
+        self.breaker(|breaker| (breaker, throttle))
+    }
+
+    pub(crate) fn hint<W2>(self, hint: W2) -> Retrier<F, Args, Fut, B, S, P, M, H, C, W2> {
+        Retrier {
+            retry: self.retry,
+            progress: self.progress,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            f: self.f,
+            state: self.state,
+            args: self.args,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            breaker: self.breaker,
+        }
+    }
+
+    pub(crate) fn breaker<C2>(
+        self,
+        breaker: impl FnOnce(C) -> C2,
+    ) -> Retrier<F, Args, Fut, B, S, P, M, H, C2, W> {
+        Retrier {
+            retry: self.retry,
+            progress: self.progress,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            f: self.f,
+            state: self.state,
+            args: self.args,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: self.hooks,
+            breaker: breaker(self.breaker),
+        }
+    }
+
+    pub(crate) fn hooks<H2>(
+        self,
+        hooks: impl FnOnce(H) -> H2,
+    ) -> Retrier<F, Args, Fut, B, S, P, M, H2, C, W> {
+        Retrier {
+            retry: self.retry,
+            progress: self.progress,
+            timeout: self.timeout,
+            deadline: self.deadline,
+            budget: self.budget,
+            cancel: self.cancel,
+            f: self.f,
+            state: self.state,
+            args: self.args,
+            backoff: self.backoff,
+            sleeper: self.sleeper,
+            predicate: self.predicate,
+            hint: self.hint,
+            outcome: self.outcome,
+            hooks: hooks(self.hooks),
+            breaker: self.breaker,
+        }
+    }
 }
 
-impl<F, Fut, Out> Future for Retrier<F, (), Fut>
+type WithHooks<F, Args, Fut, B, S, P, M, R, SH, X, C, W> =
+    Retrier<F, Args, Fut, B, S, P, M, Hooks<R, SH, X>, C, W>;
+
+#[allow(clippy::type_complexity)]
+impl<F, Args, Fut: Future, B, S: Sleeper, P, M, R, SH, X, C, W>
+    WithHooks<F, Args, Fut, B, S, P, M, R, SH, X, C, W>
+{
+    /// Call `hook` with the error, the number of the failed attempt and the
+    /// delay before the next one
+    pub fn on_retry<E, R2>(self, hook: R2) -> WithHooks<F, Args, Fut, B, S, P, M, R2, SH, X, C, W>
+    where
+        R2: FnMut(&E, usize, Duration),
+    {
+        self.hooks(|hooks| hooks.on_retry(hook))
+    }
+
+    /// Like [`on_retry`](Self::on_retry), waiting for the returned future
+    /// before the delay starts
+    ///
+    /// ```rust
+    /// use retry::future::retry::*;
+    ///
+    /// async fn fetch() -> Result<(), String> {
+    ///     Err("unavailable".into())
+    /// }
+    ///
+    /// async fn log(line: String) {
+    ///     eprintln!("{line}");
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let _ = fetch
+    ///     .retry::<3>()
+    ///     .on_retry_async(|e: &String, attempt, _| log(format!("attempt {attempt} failed: {e}")))
+    ///     .await;
+    /// # }
+    /// ```
+    pub fn on_retry_async<E, R2, HookFut>(
+        self,
+        hook: R2,
+    ) -> WithHooks<F, Args, Fut, B, S, P, M, AsyncHook<R2>, SH, X, C, W>
+    where
+        R2: FnMut(&E, usize, Duration) -> HookFut,
+        HookFut: Future<Output = ()> + Send + 'static,
+    {
+        self.hooks(|hooks| hooks.on_retry(AsyncHook(hook)))
+    }
+
+    /// Call `hook` with the number of the attempt that succeeded
+    pub fn on_success<SH2>(self, hook: SH2) -> WithHooks<F, Args, Fut, B, S, P, M, R, SH2, X, C, W>
+    where
+        SH2: FnMut(usize),
+    {
+        self.hooks(|hooks| hooks.on_success(hook))
+    }
+
+    /// Like [`on_success`](Self::on_success), waiting for the returned future
+    /// before resolving
+    pub fn on_success_async<SH2, HookFut>(
+        self,
+        hook: SH2,
+    ) -> WithHooks<F, Args, Fut, B, S, P, M, R, AsyncHook<SH2>, X, C, W>
+    where
+        SH2: FnMut(usize) -> HookFut,
+        HookFut: Future<Output = ()> + Send + 'static,
+    {
+        self.hooks(|hooks| hooks.on_success(AsyncHook(hook)))
+    }
+
+    /// Call `hook` with the last error and the number of attempts made once
+    /// the retrier gives up, whether it ran out of attempts, hit a permanent
+    /// error, the deadline, the budget, an open circuit or the throttle, or
+    /// was cancelled
+    pub fn on_exhausted<E, X2>(
+        self,
+        hook: X2,
+    ) -> WithHooks<F, Args, Fut, B, S, P, M, R, SH, X2, C, W>
+    where
+        X2: FnMut(&E, usize),
+    {
+        self.hooks(|hooks| hooks.on_exhausted(hook))
+    }
+
+    /// Like [`on_exhausted`](Self::on_exhausted), waiting for the returned
+    /// future before resolving
+    pub fn on_exhausted_async<E, X2, HookFut>(
+        self,
+        hook: X2,
+    ) -> WithHooks<F, Args, Fut, B, S, P, M, R, SH, AsyncHook<X2>, C, W>
+    where
+        X2: FnMut(&E, usize) -> HookFut,
+        HookFut: Future<Output = ()> + Send + 'static,
+    {
+        self.hooks(|hooks| hooks.on_exhausted(AsyncHook(hook)))
+    }
+}
+
+impl<F, Args, Fut, B, S, P, M, H, C, W> Future for Retrier<F, Args, Fut, B, S, P, M, H, C, W>
 where
-    F: Fn() -> Fut,
-    Fut: Future<Output = Out>,
+    F: Call<Args::Args, <Fut::Output as Tryable>::Error, Output = Fut>,
+    Args: ArgSource,
+    Fut: Future,
+    Fut::Output: Tryable,
+    B: Backoff,
+    S: Sleeper,
+    P: RetryIf<<Fut::Output as Tryable>::Error>,
+    W: ReadHint<<Fut::Output as Tryable>::Error>,
+    M: Outcome<Fut::Output>,
+    H: AsyncNotify<<Fut::Output as Tryable>::Error>,
+    C: Breaker<Fut::Output>,
 {
-    type Output = Out;
+    type Output = M::Output;
     fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
         let mut this = self.project();
-        let mut tries = *this.retry;
-        match this.state.as_mut().project() {
-            RetryStates::Pending => {
-                // Create the future from the function
-                let fut = (this.f)();
-                this.state.set(RetryState::Ready(fut));
-                cx.waker().wake_by_ref();
-                Poll::Pending
-            }
-
-            RetryStates::Ready(ref mut fut) => {
-                match Pin::new(fut).poll(cx) {
-                    // If it's already ready then decrement the repeat counter for 1
-                    Poll::Ready(v) => {
-                        tries -= 1;
-                        if tries == 0 {
-                            Poll::Ready(v)
-                        } else {
-                            // So we need to retry more so create a new future
-                            let fut = (this.f)();
-                            this.state.set(RetryState::Ready(fut));
-                            *this.retry = tries;
-                            cx.waker().wake_by_ref();
-                            Poll::Pending
+        let progress = this.progress;
+        loop {
+            match this.state.as_mut().project() {
+                RetryStates::Pending => {
+                    // Cancellation is only checked between attempts, the first one
+                    // is always made
+                    let stopped = match progress.previous.take() {
+                        Some(previous) if cancelled(this.cancel) => {
+                            Some((previous, Stop::Cancelled))
+                        }
+                        previous => {
+                            progress.previous = previous;
+                            this.breaker.admit(progress.attempts).err()
+                        }
+                    };
+                    if let Some((refused, stop)) = stopped {
+                        let output = progress.previous.take().unwrap_or(refused);
+                        let notify = output
+                            .error()
+                            .and_then(|error| this.hooks.exhausted(error, progress.attempts));
+                        match notify {
+                            Some(hook) => {
+                                progress.finished = Some((output, stop));
+                                this.state.set(RetryState::Notifying(hook));
+                                continue;
+                            }
+                            None => return Poll::Ready(M::report(output, stop)),
+                        }
+                    }
+                    // Create the future from the function and poll it straight away
+                    let started = progress.started.get_or_insert_with(Instant::now);
+                    let attempt = Attempt {
+                        number: progress.attempts + 1,
+                        elapsed: started.elapsed(),
+                        previous_error: progress.previous.as_ref().and_then(Tryable::error),
+                    };
+                    let args = this.args.args(attempt.number);
+                    let fut = this.f.call(attempt, args);
+                    let timer = this.timeout.map(|(limit, _)| this.sleeper.sleep(limit));
+                    this.state.set(RetryState::Ready(fut, timer));
+                }
+                RetryStates::Ready(fut, timer) => {
+                    // Only the inner future and the timer register a wakeup, so
+                    // a pending attempt doesn't spin the executor
+                    let v = match (fut.poll(cx), timer.as_pin_mut()) {
+                        (Poll::Ready(v), _) => v,
+                        (Poll::Pending, None) => return Poll::Pending,
+                        (Poll::Pending, Some(timer)) => {
+                            ready!(timer.poll(cx));
+                            let (_, timed_out) = this.timeout.expect("timer without a timeout");
+                            timed_out()
+                        }
+                    };
+                    // Drop the attempt, cancelling it if it timed out
+                    this.state.set(RetryState::Pending);
+                    this.breaker.record(&v);
+                    progress.attempts += 1;
+                    let attempts = progress.attempts;
+                    // Stop on success, on a permanent error or once the retries are used
+                    // up, running the hook for giving up on every failure
+                    let (stop, notify) = match v.error() {
+                        None => {
+                            if let Some(budget) = this.budget {
+                                budget.deposit();
+                            }
+                            (Stop::Success, this.hooks.success(attempts))
+                        }
+                        Some(error) if !this.predicate.should_retry(error) => {
+                            (Stop::Permanent, this.hooks.exhausted(error, attempts))
+                        }
+                        Some(error) if *this.retry == 0 => (
+                            Stop::Exhausted { attempts },
+                            this.hooks.exhausted(error, attempts),
+                        ),
+                        Some(error) => {
+                            *this.retry -= 1;
+                            progress.delay = match this.hint.retry_after(error) {
+                                Some(delay) => delay,
+                                None => this.backoff.next().unwrap_or_default(),
+                            };
+                            let timed_out = this.deadline.zip(progress.started).is_some_and(
+                                |(deadline, started)| deadline.crossed_by(started, progress.delay),
+                            );
+                            if timed_out {
+                                (Stop::TimedOut, this.hooks.exhausted(error, attempts))
+                            } else if cancelled(this.cancel) {
+                                (Stop::Cancelled, this.hooks.exhausted(error, attempts))
+                            } else if this
+                                .budget
+                                .as_ref()
+                                .is_some_and(|budget| !budget.withdraw())
+                            {
+                                let notify = this.hooks.exhausted(error, attempts);
+                                (Stop::Throttled { attempts }, notify)
+                            } else {
+                                let notify = this.hooks.retry(error, attempts, progress.delay);
+                                progress.previous = Some(v);
+                                match notify {
+                                    Some(hook) => this.state.set(RetryState::Notifying(hook)),
+                                    None if progress.delay.is_zero() => {
+                                        this.state.set(RetryState::Pending)
+                                    }
+                                    None => this.state.set(RetryState::Sleeping(
+                                        this.sleeper.sleep(progress.delay),
+                                    )),
+                                }
+                                continue;
+                            }
                         }
+                    };
+                    match notify {
+                        Some(hook) => {
+                            progress.finished = Some((v, stop));
+                            this.state.set(RetryState::Notifying(hook));
+                        }
+                        None => return Poll::Ready(M::report(v, stop)),
                     }
-                    Poll::Pending => {
-                        cx.waker().wake_by_ref();
-                        Poll::Pending
+                }
+                RetryStates::Sleeping(delay) => {
+                    ready!(delay.poll(cx));
+                    this.state.set(RetryState::Pending);
+                }
+                RetryStates::Notifying(hook) => {
+                    ready!(hook.as_mut().poll(cx));
+                    if let Some((v, stop)) = progress.finished.take() {
+                        return Poll::Ready(M::report(v, stop));
+                    }
+                    if progress.delay.is_zero() {
+                        this.state.set(RetryState::Pending);
+                    } else {
+                        this.state
+                            .set(RetryState::Sleeping(this.sleeper.sleep(progress.delay)));
                     }
                 }
             }
@@ -61,93 +691,62 @@ where
     }
 }
 
-pub trait AsyncRetry0<Fut>: Sized {
-    fn retry<const N: usize>(self) -> Retrier<Self, (), Fut>;
+pub trait AsyncRetry0<Fut: Future>: Sized {
+    /// Retry with a count read at runtime, see [`Attempts`]
+    fn retry_n(self, attempts: impl Into<Attempts>) -> Retrier<Self, (), Fut> {
+        Retrier::new(attempts.into(), self, ())
+    }
+
+    fn retry<const N: usize>(self) -> Retrier<Self, (), Fut> {
+        self.retry_n(N)
+    }
 }
 
 impl<F, Fut, Out> AsyncRetry0<Fut> for F
 where
-    F: Fn() -> Out,
+    F: FnMut() -> Fut,
     Fut: Future<Output = Out>,
+    Out: Tryable,
+{
+}
+
+impl<F, E, Fut> AsyncRetry0<Fut> for WithAttempt<F, E>
+where
+    F: FnMut(Attempt<'_, E>) -> Fut,
+    Fut: Future,
+    Fut::Output: Tryable<Error = E>,
 {
-    fn retry<const N: usize>(self) -> Retrier<Self, (), Fut> {
-        Retrier {
-            retry: N,
-            f: self,
-            state: RetryState::Pending,
-            args: (),
-        }
-    }
 }
 
 macro_rules! impl_gen_async_retry {
     ($name: ident, $($item: ident),*) => {
         #[allow(non_snake_case, clippy::too_many_arguments)]
-        pub trait $name<Fut, $($item),*>: Sized {
+        pub trait $name<Fut: Future, $($item),*>: Sized {
+            /// Retry with a count read at runtime, see [`Attempts`]
+            fn retry_n(self, attempts: impl Into<Attempts>, $($item: $item),*) -> Retrier<Self, ($($item),*,), Fut> {
+                Retrier::new(attempts.into(), self, ($($item),*,))
+            }
+
             fn retry<const N: usize>(self, $($item: $item),*) -> Retrier<Self, ($($item),*,), Fut> {
-                Retrier {
-                    retry: N,
-                    f: self,
-                    state: RetryState::Pending,
-                    args: ($($item),*,),
-                }
+                self.retry_n(N, $($item),*)
             }
         }
 
         #[allow(non_snake_case)]
         impl<F, Fut, Out, $($item),*> $name<Fut, $($item),*> for F
         where
-            F: Fn($($item),*) -> Fut,
+            F: FnMut($($item),*) -> Fut,
             Fut: Future<Output = Out>,
+            Out: Tryable,
         { }
 
         #[allow(non_snake_case)]
-        impl<F, Fut, Out, $($item: Clone),*> Future for Retrier<F, ($($item),*,), Fut>
-            where
-                F: Fn($($item),*) -> Fut,
-                Fut: Future<Output = Out>,
-        {
-            type Output = Out;
-            fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-                let mut this = self.project();
-                let mut tries = *this.retry;
-                match this.state.as_mut().project() {
-                    RetryStates::Pending => {
-                        // Create the future from the function
-                        let ( $($item),*, ) = this.args.clone();
-                        let fut = (this.f)($($item),*,);
-                        this.state.set(RetryState::Ready(fut));
-                        cx.waker().wake_by_ref();
-                        Poll::Pending
-                    }
-
-                    RetryStates::Ready(ref mut fut) => {
-                        match Pin::new(fut).poll(cx) {
-                            // If it's already ready then decrement the repeat counter for 1
-                            Poll::Ready(v) => {
-                                tries -= 1;
-                                if tries == 0 {
-                                    Poll::Ready(v)
-                                } else {
-                                    let ( $($item),*, ) = this.args.clone();
-                                    let fut = (this.f)($($item),*,);
-                                    this.state.set(RetryState::Ready(fut));
-                                    *this.retry = tries;
-                                    cx.waker().wake_by_ref();
-                                    Poll::Pending
-                                }
-                            }
-                            Poll::Pending => {
-                                cx.waker().wake_by_ref();
-                                Poll::Pending
-                            }
-                        }
-                    }
-                }
-            }
-
-         }
-
+        impl<F, E, Fut, $($item),*> $name<Fut, $($item),*> for WithAttempt<F, E>
+        where
+            F: FnMut(Attempt<'_, E>, $($item),*) -> Fut,
+            Fut: Future,
+            Fut::Output: Tryable<Error = E>,
+        { }
     }
 }
 
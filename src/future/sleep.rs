@@ -0,0 +1,224 @@
+//! Timers used to wait between attempts.
+//!
+//! The async retrier doesn't assume a runtime, it asks a [`Sleeper`] for a
+//! future that completes after the delay. [`ThreadSleeper`] works anywhere,
+//! the runtime specific sleepers are behind the `tokio`, `async-std` and
+//! `smol` features, and any `Fn(Duration) -> impl Future` can be used as well.
+//!
+//! ```rust
+//! use core::time::Duration;
+//! use retry::backoff::Constant;
+//! use retry::future::retry::*;
+//!
+//! async fn fetch() -> Result<(), ()> {
+//!     Ok(())
+//! }
+//! # #[tokio::main]
+//! # async fn main() {
+//! fetch
+//!     .retry::<3>()
+//!     .backoff(Constant::new(Duration::from_millis(100)))
+//!     .sleeper(tokio::time::sleep)
+//!     .await
+//!     .unwrap();
+//! # }
+//! ```
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+use std::collections::BTreeMap;
+use std::sync::{Condvar, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::Instant;
+
+pub trait Sleeper {
+    type Sleep: Future;
+    fn sleep(&self, duration: Duration) -> Self::Sleep;
+}
+
+impl<F, Fut> Sleeper for F
+where
+    F: Fn(Duration) -> Fut,
+    Fut: Future,
+{
+    type Sleep = Fut;
+    fn sleep(&self, duration: Duration) -> Fut {
+        self(duration)
+    }
+}
+
+/// Sleeps on a timer thread shared by the whole process, so it works without
+/// any runtime
+///
+/// The timer of the runtime the retrier runs on, set with `.sleeper(..)`,
+/// saves waking that thread.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper {
+    type Sleep = Delay;
+    fn sleep(&self, duration: Duration) -> Delay {
+        Delay::new(duration)
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioSleeper;
+
+#[cfg(feature = "tokio")]
+impl Sleeper for TokioSleeper {
+    type Sleep = tokio::time::Sleep;
+    fn sleep(&self, duration: Duration) -> tokio::time::Sleep {
+        tokio::time::sleep(duration)
+    }
+}
+
+#[cfg(feature = "async-std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsyncStdSleeper;
+
+#[cfg(feature = "async-std")]
+impl Sleeper for AsyncStdSleeper {
+    type Sleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        Box::pin(async_std::task::sleep(duration))
+    }
+}
+
+#[cfg(feature = "smol")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmolSleeper;
+
+#[cfg(feature = "smol")]
+impl Sleeper for SmolSleeper {
+    type Sleep = smol::Timer;
+    fn sleep(&self, duration: Duration) -> smol::Timer {
+        smol::Timer::after(duration)
+    }
+}
+
+/// The delays waiting on the timer thread, by deadline then by id
+#[derive(Debug, Default)]
+struct Queue {
+    next_id: u64,
+    wakers: BTreeMap<(Instant, u64), Waker>,
+}
+
+#[derive(Debug, Default)]
+struct Timer {
+    queue: Mutex<Queue>,
+    changed: Condvar,
+}
+
+/// The timer thread shared by every [`Delay`], started on first use
+fn timer() -> &'static Timer {
+    static TIMER: OnceLock<Timer> = OnceLock::new();
+    TIMER.get_or_init(|| {
+        std::thread::Builder::new()
+            .name("retry-timer".into())
+            .spawn(|| timer().run())
+            .expect("failed to spawn the timer thread");
+        Timer::default()
+    })
+}
+
+impl Timer {
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn run(&self) {
+        let mut queue = self.lock();
+        loop {
+            let now = Instant::now();
+            let mut due = Vec::new();
+            while let Some(entry) = queue.wakers.first_entry() {
+                if entry.key().0 > now {
+                    break;
+                }
+                due.push(entry.remove());
+            }
+            if !due.is_empty() {
+                // Wake outside of the lock, in case a waker polls right away
+                drop(queue);
+                due.into_iter().for_each(Waker::wake);
+                queue = self.lock();
+                continue;
+            }
+            queue = match queue.wakers.keys().next() {
+                Some(&(deadline, _)) => {
+                    let timeout = deadline.saturating_duration_since(now);
+                    let (queue, _) = self
+                        .changed
+                        .wait_timeout(queue, timeout)
+                        .unwrap_or_else(PoisonError::into_inner);
+                    queue
+                }
+                None => self
+                    .changed
+                    .wait(queue)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
+
+/// Runtime agnostic timer waiting on a thread shared by every delay
+///
+/// Dropping the delay before it completes takes it off the timer thread.
+#[derive(Debug)]
+pub struct Delay {
+    deadline: Instant,
+    /// Set once the delay waits on the timer thread
+    id: Option<u64>,
+}
+
+impl Delay {
+    pub fn new(duration: Duration) -> Self {
+        let now = Instant::now();
+        Delay {
+            // Durations too long for an `Instant` never complete in practice
+            deadline: now
+                .checked_add(duration)
+                .unwrap_or_else(|| now + Duration::from_secs(u32::MAX.into())),
+            id: None,
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        if Instant::now() >= this.deadline {
+            if let Some(id) = this.id.take() {
+                timer().lock().wakers.remove(&(this.deadline, id));
+            }
+            return Poll::Ready(());
+        }
+        let timer = timer();
+        let mut queue = timer.lock();
+        let id = *this.id.get_or_insert_with(|| {
+            queue.next_id += 1;
+            queue.next_id
+        });
+        let key = (this.deadline, id);
+        let earliest = queue.wakers.keys().next().is_none_or(|first| key <= *first);
+        queue.wakers.insert(key, cx.waker().clone());
+        drop(queue);
+        if earliest {
+            timer.changed.notify_one();
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Delay {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            timer().lock().wakers.remove(&(self.deadline, id));
+        }
+    }
+}
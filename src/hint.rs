@@ -0,0 +1,241 @@
+//! Waiting as long as the server asks to.
+//!
+//! Rate-limited APIs often say when to try again, e.g. with the HTTP
+//! `Retry-After` header. An error implementing [`RetryHint`] carries that
+//! delay, and `.retry_hint()` on the builder and async retriers makes them
+//! wait for it instead of the next delay of their backoff. Errors without a
+//! hint still wait for the backoff.
+//!
+//! [`RetryAfter`] parses the value of a `Retry-After` header, either a number
+//! of seconds or an HTTP-date:
+//!
+//! ```rust
+//! use core::time::Duration;
+//! use std::time::{SystemTime, UNIX_EPOCH};
+//! use retry::hint::{RetryAfter, RetryHint};
+//!
+//! struct RateLimited {
+//!     retry_after: Option<RetryAfter>,
+//! }
+//!
+//! impl RetryHint for RateLimited {
+//!     fn retry_after(&self) -> Option<Duration> {
+//!         self.retry_after.map(|retry_after| retry_after.delay())
+//!     }
+//! }
+//!
+//! let error = RateLimited {
+//!     retry_after: RetryAfter::parse("120"),
+//! };
+//! assert_eq!(error.retry_after(), Some(Duration::from_secs(120)));
+//!
+//! let date = RetryAfter::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+//! let now = UNIX_EPOCH + Duration::from_secs(784_111_700);
+//! assert_eq!(date.delay_from(now), Duration::from_secs(77));
+//! assert_eq!(date.delay_from(SystemTime::now()), Duration::ZERO);
+//! ```
+
+use core::str::FromStr;
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// An error telling how long to wait before the next attempt
+pub trait RetryHint {
+    /// The delay the server asked for, `None` to fall back on the backoff
+    fn retry_after(&self) -> Option<Duration>;
+}
+
+impl<T: RetryHint + ?Sized> RetryHint for &T {
+    fn retry_after(&self) -> Option<Duration> {
+        (**self).retry_after()
+    }
+}
+
+impl<T: RetryHint + ?Sized> RetryHint for Box<T> {
+    fn retry_after(&self) -> Option<Duration> {
+        (**self).retry_after()
+    }
+}
+
+/// How a retrier reads the delay a failed attempt asks for
+pub trait ReadHint<E> {
+    fn retry_after(&self, error: &E) -> Option<Duration>;
+}
+
+/// Wait for the backoff whatever the error, the default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoHint;
+
+impl<E> ReadHint<E> for NoHint {
+    fn retry_after(&self, _: &E) -> Option<Duration> {
+        None
+    }
+}
+
+/// Wait for the [`RetryHint`] of the errors, set with `.retry_hint()`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hinted;
+
+impl<E: RetryHint> ReadHint<E> for Hinted {
+    fn retry_after(&self, error: &E) -> Option<Duration> {
+        error.retry_after()
+    }
+}
+
+/// The value of a `Retry-After` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryAfter {
+    /// A number of seconds
+    Delay(Duration),
+    /// An HTTP-date
+    Date(SystemTime),
+}
+
+impl RetryAfter {
+    /// Parse delta-seconds or an HTTP-date in any of the three formats of
+    /// RFC 9110, `None` if the value is neither
+    ///
+    /// Delta-seconds past what a `u64` holds saturate to [`Duration::MAX`].
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+            // Only too many seconds for a u64 fail to parse
+            let delay = value.parse().map_or(Duration::MAX, Duration::from_secs);
+            return Some(RetryAfter::Delay(delay));
+        }
+        parse_http_date(value).map(RetryAfter::Date)
+    }
+
+    /// How long to wait from now
+    pub fn delay(&self) -> Duration {
+        self.delay_from(SystemTime::now())
+    }
+
+    /// How long to wait from `now`, zero for a date in the past
+    pub fn delay_from(&self, now: SystemTime) -> Duration {
+        match self {
+            RetryAfter::Delay(delay) => *delay,
+            RetryAfter::Date(date) => date.duration_since(now).unwrap_or_default(),
+        }
+    }
+}
+
+/// The header value isn't delta-seconds nor an HTTP-date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InvalidRetryAfter;
+
+impl core::fmt::Display for InvalidRetryAfter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid Retry-After value")
+    }
+}
+
+impl std::error::Error for InvalidRetryAfter {}
+
+impl FromStr for RetryAfter {
+    type Err = InvalidRetryAfter;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RetryAfter::parse(s).ok_or(InvalidRetryAfter)
+    }
+}
+
+/// Parse an IMF-fixdate (`Sun, 06 Nov 1994 08:49:37 GMT`), or one of the
+/// obsolete RFC 850 (`Sunday, 06-Nov-94 08:49:37 GMT`) and asctime
+/// (`Sun Nov  6 08:49:37 1994`) formats
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    let fields: Vec<&str> = value.split_whitespace().collect();
+    let (day, month, year, time) = match fields[..] {
+        [weekday, day, month, year, time, "GMT"] if named(weekday, &WEEKDAYS) => {
+            (day, month, digits(year, 4)?, time)
+        }
+        [weekday, date, time, "GMT"] if named(weekday, &LONG_WEEKDAYS) => {
+            let mut date = date.split('-');
+            let (day, month, year) = (date.next()?, date.next()?, date.next()?);
+            if date.next().is_some() {
+                return None;
+            }
+            // Two digit years are read as the closest to 1970 onwards
+            let year = digits(year, 2)?;
+            (
+                day,
+                month,
+                if year < 70 { 2000 + year } else { 1900 + year },
+                time,
+            )
+        }
+        [weekday, month, day, time, year] if WEEKDAYS.contains(&weekday) => {
+            (day, month, digits(year, 4)?, time)
+        }
+        _ => return None,
+    };
+
+    let day: u32 = day.parse().ok()?;
+    let month = MONTHS.iter().position(|m| *m == month)? as u32 + 1;
+    let mut time = time.split(':').map(|field| field.parse::<u32>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if time.next().is_some() || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+    // Allow for a leap second
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    let seconds = days * 86400 + i64::from(hour * 3600 + minute * 60 + second);
+    // Dates before the epoch are in the past all the same
+    Some(UNIX_EPOCH + Duration::from_secs(seconds.max(0) as u64))
+}
+
+/// Whether `field` is one of `names` followed by a comma
+fn named(field: &str, names: &[&str]) -> bool {
+    field
+        .strip_suffix(',')
+        .is_some_and(|name| names.contains(&name))
+}
+
+/// `field` as a number if it's exactly `len` ASCII digits, without a sign
+fn digits(field: &str, len: usize) -> Option<i64> {
+    if field.len() != len || !field.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    field.parse().ok()
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+const LONG_WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
@@ -0,0 +1,276 @@
+//! Observing the attempts of a retrier.
+//!
+//! Hooks are set with `.on_retry(..)`, `.on_success(..)` and
+//! `.on_exhausted(..)` on the builder and async retriers:
+//!
+//! - `on_retry(&error, attempt, next_delay)` runs after every failed attempt
+//!   that is going to be retried, before waiting `next_delay`
+//! - `on_success(attempt)` runs once an attempt succeeds
+//! - `on_exhausted(&error, attempts)` runs whenever the retrier gives up on
+//!   a failure, be it the last attempt, a permanent error, the deadline, the
+//!   retry budget, an open circuit or the throttle
+//!
+//! Attempts are counted from 1. The async retrier also takes hooks returning
+//! a future through the `_async` variants of those methods, the future is
+//! awaited before the retrier carries on.
+
+use core::time::Duration;
+
+pub trait OnRetry<E> {
+    fn on_retry(&mut self, error: &E, attempt: usize, next_delay: Duration);
+}
+
+impl<E> OnRetry<E> for () {
+    fn on_retry(&mut self, _: &E, _: usize, _: Duration) {}
+}
+
+impl<E, F> OnRetry<E> for F
+where
+    F: FnMut(&E, usize, Duration),
+{
+    fn on_retry(&mut self, error: &E, attempt: usize, next_delay: Duration) {
+        self(error, attempt, next_delay)
+    }
+}
+
+pub trait OnSuccess {
+    fn on_success(&mut self, attempt: usize);
+}
+
+impl OnSuccess for () {
+    fn on_success(&mut self, _: usize) {}
+}
+
+impl<F> OnSuccess for F
+where
+    F: FnMut(usize),
+{
+    fn on_success(&mut self, attempt: usize) {
+        self(attempt)
+    }
+}
+
+pub trait OnExhausted<E> {
+    fn on_exhausted(&mut self, error: &E, attempts: usize);
+}
+
+impl<E> OnExhausted<E> for () {
+    fn on_exhausted(&mut self, _: &E, _: usize) {}
+}
+
+impl<E, F> OnExhausted<E> for F
+where
+    F: FnMut(&E, usize),
+{
+    fn on_exhausted(&mut self, error: &E, attempts: usize) {
+        self(error, attempts)
+    }
+}
+
+/// The hooks set on a retrier, `()` for the ones that aren't
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hooks<R = (), S = (), X = ()> {
+    pub(crate) on_retry: R,
+    pub(crate) on_success: S,
+    pub(crate) on_exhausted: X,
+}
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+impl<R, S, X> Hooks<R, S, X> {
+    pub(crate) fn on_retry<R2>(self, on_retry: R2) -> Hooks<R2, S, X> {
+        Hooks {
+            on_retry,
+            on_success: self.on_success,
+            on_exhausted: self.on_exhausted,
+        }
+    }
+
+    pub(crate) fn on_success<S2>(self, on_success: S2) -> Hooks<R, S2, X> {
+        Hooks {
+            on_retry: self.on_retry,
+            on_success,
+            on_exhausted: self.on_exhausted,
+        }
+    }
+
+    pub(crate) fn on_exhausted<X2>(self, on_exhausted: X2) -> Hooks<R, S, X2> {
+        Hooks {
+            on_retry: self.on_retry,
+            on_success: self.on_success,
+            on_exhausted,
+        }
+    }
+}
+
+/// Runs the hooks of a retrier
+pub trait Notify<E> {
+    fn retry(&mut self, error: &E, attempt: usize, next_delay: Duration);
+    fn success(&mut self, attempt: usize);
+    fn exhausted(&mut self, error: &E, attempts: usize);
+}
+
+impl<E, R, S, X> Notify<E> for Hooks<R, S, X>
+where
+    R: OnRetry<E>,
+    S: OnSuccess,
+    X: OnExhausted<E>,
+{
+    fn retry(&mut self, error: &E, attempt: usize, next_delay: Duration) {
+        self.on_retry.on_retry(error, attempt, next_delay)
+    }
+    fn success(&mut self, attempt: usize) {
+        self.on_success.on_success(attempt)
+    }
+    fn exhausted(&mut self, error: &E, attempts: usize) {
+        self.on_exhausted.on_exhausted(error, attempts)
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use self::future::*;
+
+#[cfg(feature = "futures")]
+mod future {
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::time::Duration;
+
+    use super::Hooks;
+
+    pub type HookFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    /// A hook returning a future, see the `_async` hook methods of
+    /// [`future::retry::Retrier`](crate::future::retry::Retrier)
+    #[derive(Debug, Clone, Copy)]
+    pub struct AsyncHook<F>(pub(crate) F);
+
+    pub trait AsyncOnRetry<E> {
+        fn on_retry(
+            &mut self,
+            error: &E,
+            attempt: usize,
+            next_delay: Duration,
+        ) -> Option<HookFuture>;
+    }
+
+    impl<E> AsyncOnRetry<E> for () {
+        fn on_retry(&mut self, _: &E, _: usize, _: Duration) -> Option<HookFuture> {
+            None
+        }
+    }
+
+    impl<E, F> AsyncOnRetry<E> for F
+    where
+        F: FnMut(&E, usize, Duration),
+    {
+        fn on_retry(
+            &mut self,
+            error: &E,
+            attempt: usize,
+            next_delay: Duration,
+        ) -> Option<HookFuture> {
+            self(error, attempt, next_delay);
+            None
+        }
+    }
+
+    impl<E, F, Fut> AsyncOnRetry<E> for AsyncHook<F>
+    where
+        F: FnMut(&E, usize, Duration) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        fn on_retry(
+            &mut self,
+            error: &E,
+            attempt: usize,
+            next_delay: Duration,
+        ) -> Option<HookFuture> {
+            Some(Box::pin((self.0)(error, attempt, next_delay)))
+        }
+    }
+
+    pub trait AsyncOnSuccess {
+        fn on_success(&mut self, attempt: usize) -> Option<HookFuture>;
+    }
+
+    impl AsyncOnSuccess for () {
+        fn on_success(&mut self, _: usize) -> Option<HookFuture> {
+            None
+        }
+    }
+
+    impl<F> AsyncOnSuccess for F
+    where
+        F: FnMut(usize),
+    {
+        fn on_success(&mut self, attempt: usize) -> Option<HookFuture> {
+            self(attempt);
+            None
+        }
+    }
+
+    impl<F, Fut> AsyncOnSuccess for AsyncHook<F>
+    where
+        F: FnMut(usize) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        fn on_success(&mut self, attempt: usize) -> Option<HookFuture> {
+            Some(Box::pin((self.0)(attempt)))
+        }
+    }
+
+    pub trait AsyncOnExhausted<E> {
+        fn on_exhausted(&mut self, error: &E, attempts: usize) -> Option<HookFuture>;
+    }
+
+    impl<E> AsyncOnExhausted<E> for () {
+        fn on_exhausted(&mut self, _: &E, _: usize) -> Option<HookFuture> {
+            None
+        }
+    }
+
+    impl<E, F> AsyncOnExhausted<E> for F
+    where
+        F: FnMut(&E, usize),
+    {
+        fn on_exhausted(&mut self, error: &E, attempts: usize) -> Option<HookFuture> {
+            self(error, attempts);
+            None
+        }
+    }
+
+    impl<E, F, Fut> AsyncOnExhausted<E> for AsyncHook<F>
+    where
+        F: FnMut(&E, usize) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        fn on_exhausted(&mut self, error: &E, attempts: usize) -> Option<HookFuture> {
+            Some(Box::pin((self.0)(error, attempts)))
+        }
+    }
+
+    /// Runs the hooks of an async retrier, returning the future to await
+    /// for the async ones
+    pub trait AsyncNotify<E> {
+        fn retry(&mut self, error: &E, attempt: usize, next_delay: Duration) -> Option<HookFuture>;
+        fn success(&mut self, attempt: usize) -> Option<HookFuture>;
+        fn exhausted(&mut self, error: &E, attempts: usize) -> Option<HookFuture>;
+    }
+
+    impl<E, R, S, X> AsyncNotify<E> for Hooks<R, S, X>
+    where
+        R: AsyncOnRetry<E>,
+        S: AsyncOnSuccess,
+        X: AsyncOnExhausted<E>,
+    {
+        fn retry(&mut self, error: &E, attempt: usize, next_delay: Duration) -> Option<HookFuture> {
+            self.on_retry.on_retry(error, attempt, next_delay)
+        }
+        fn success(&mut self, attempt: usize) -> Option<HookFuture> {
+            self.on_success.on_success(attempt)
+        }
+        fn exhausted(&mut self, error: &E, attempts: usize) -> Option<HookFuture> {
+            self.on_exhausted.on_exhausted(error, attempts)
+        }
+    }
+}
@@ -2,13 +2,38 @@
 //!
 //! Retry any sync / async function with 
 
+pub mod attempt;
+pub mod backoff;
+pub mod budget;
 #[cfg(feature = "builder")]
 pub mod builder;
+#[cfg(any(feature = "builder", feature = "futures"))]
+mod call;
+pub mod cancel;
+pub mod circuit;
+pub mod error;
 #[cfg(feature = "futures")]
 pub mod future;
-pub(crate) mod tryable;
+pub mod hint;
+pub mod hooks;
+pub mod oneshot;
+pub mod predicate;
+mod rng;
+pub mod throttle;
+pub mod tryable;
+pub mod tuple;
 
-mod oneshot;
+pub use attempt::{with_attempt, Attempt, Attempts};
+pub use error::RetryError;
+pub use tryable::Tryable;
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+mod deadline;
+/// The per-arity traits for the `f.retry::<N>(a, b)` call syntax. The tuple
+/// based traits come with `use retry::tuple::*`, see [`tuple`](mod@tuple)
 pub mod prelude {
     pub use crate::oneshot::*;
+    pub use crate::tuple::args_fn;
+    #[cfg(feature = "futures")]
+    pub use crate::tuple::AsyncHedge;
 }
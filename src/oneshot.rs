@@ -1,9 +1,42 @@
+//! One trait per arity, up to 10 arguments, for the `f.retry::<N>(a, b)` call
+//! syntax. The [`prelude`](crate::prelude) exports them. They forward to the
+//! tuple based traits in [`crate::tuple`], and the two can't be glob imported
+//! in the same scope.
+//!
+//! ```rust
+//! use retry::prelude::*;
+//!
+//! fn add(a: i32, b: i32) -> Result<i32, ()> {
+//!     Ok(a + b)
+//! }
+//!
+//! assert_eq!(add.retry::<3>(1, 2), Ok(3));
+//! ```
+
+use crate::attempt::Attempts;
+use crate::error::RetryError;
 use crate::tryable::Tryable;
+use crate::tuple::{Repeat, Retry};
+
 macro_rules! impl_gen_retry_for_tuple {
     ($name: ident, $($item: ident),*) => (
         #[allow(non_snake_case, clippy::too_many_arguments)]
         pub trait $name<$($item),*, Output>: Sized {
-            fn retry<const N: usize>(self, $($item: $item),*) -> Output;
+            /// Retry with a count read at runtime, see [`Attempts`]
+            fn retry_n(self, attempts: impl Into<Attempts>, $($item: $item),*) -> Output;
+            fn try_retry_n(self, attempts: impl Into<Attempts>, $($item: $item),*) -> Result<Output::Ok, RetryError<Output::Error>>
+            where
+                Output: Tryable;
+
+            fn retry<const N: usize>(self, $($item: $item),*) -> Output {
+                self.retry_n(N, $($item),*)
+            }
+            fn try_retry<const N: usize>(self, $($item: $item),*) -> Result<Output::Ok, RetryError<Output::Error>>
+            where
+                Output: Tryable,
+            {
+                self.try_retry_n(N, $($item),*)
+            }
         }
         #[allow(non_snake_case)]
         impl<F, $($item: Clone),*, Output> $name<$($item),*, Output> for F
@@ -11,26 +44,35 @@ macro_rules! impl_gen_retry_for_tuple {
             F: FnMut($($item),*) -> Output,
             Output: Tryable,
         {
-            fn retry<const N: usize>(mut self, $($item: $item),*) -> Output {
-                let mut retry = N;
-                loop {
-                    let result = self($($item.clone()),*);
-                    if result.negative() {
-                        if retry == 0 {
-                            return result;
-                        }
-                        retry -= 1;
-                    } else {
-                        return result;
-                    }
-                }
+            fn retry_n(self, attempts: impl Into<Attempts>, $($item: $item),*) -> Output {
+                Retry::retry_n(self, attempts, ($($item,)*))
+            }
+            fn try_retry_n(self, attempts: impl Into<Attempts>, $($item: $item),*) -> Result<Output::Ok, RetryError<Output::Error>> {
+                Retry::try_retry_n(self, attempts, ($($item,)*))
             }
         }
     )
 }
 
 pub trait RetryOneshot0<Output>: Sized {
-    fn retry<const N: usize>(self) -> Output;
+    /// Retry with a count read at runtime, see [`Attempts`]
+    fn retry_n(self, attempts: impl Into<Attempts>) -> Output;
+    fn try_retry_n(
+        self,
+        attempts: impl Into<Attempts>,
+    ) -> Result<Output::Ok, RetryError<Output::Error>>
+    where
+        Output: Tryable;
+
+    fn retry<const N: usize>(self) -> Output {
+        self.retry_n(N)
+    }
+    fn try_retry<const N: usize>(self) -> Result<Output::Ok, RetryError<Output::Error>>
+    where
+        Output: Tryable,
+    {
+        self.try_retry_n(N)
+    }
 }
 
 impl<F, Output> RetryOneshot0<Output> for F
@@ -38,19 +80,14 @@ where
     F: FnMut() -> Output,
     Output: Tryable,
 {
-    fn retry<const N: usize>(mut self) -> Output {
-        let mut retry = N;
-        loop {
-            let result = self();
-            if result.negative() {
-                if retry == 0 {
-                    return result;
-                }
-                retry -= 1;
-            } else {
-                return result;
-            }
-        }
+    fn retry_n(self, attempts: impl Into<Attempts>) -> Output {
+        Retry::retry_n(self, attempts, ())
+    }
+    fn try_retry_n(
+        self,
+        attempts: impl Into<Attempts>,
+    ) -> Result<Output::Ok, RetryError<Output::Error>> {
+        Retry::try_retry_n(self, attempts, ())
     }
 }
 
@@ -69,7 +106,12 @@ macro_rules! impl_gen_repeat_for_tuple {
     ($name: ident, $($item: ident),*) => {
         #[allow(non_snake_case, clippy::too_many_arguments)]
         pub trait $name<$($item),*, Output>: Sized {
-            fn repeat<const N: usize>(&mut self, $($item: $item),*) -> Output;
+            /// Call the function `times` times, read at runtime, and at least once
+            fn repeat_n(&mut self, times: usize, $($item: $item),*) -> Output;
+
+            fn repeat<const N: usize>(&mut self, $($item: $item),*) -> Output {
+                self.repeat_n(N, $($item),*)
+            }
         }
 
         #[allow(non_snake_case)]
@@ -77,15 +119,8 @@ macro_rules! impl_gen_repeat_for_tuple {
         where
             F: FnMut($($item),*) -> Output,
         {
-            fn repeat<const N: usize>(&mut self, $($item: $item),*) -> Output {
-                let mut repeat = N;
-                loop {
-                    let res = self($($item.clone()),*);
-                    repeat -= 1;
-                    if repeat == 0 {
-                        break res;
-                    }
-                }
+            fn repeat_n(&mut self, times: usize, $($item: $item),*) -> Output {
+                Repeat::repeat_n(self, times, ($($item,)*))
             }
         }
 
@@ -93,22 +128,20 @@ macro_rules! impl_gen_repeat_for_tuple {
 }
 
 pub trait RepeatOneshot0<Output>: Sized {
-    fn repeat<const N: usize>(&mut self) -> Output;
+    /// Call the function `times` times, read at runtime, and at least once
+    fn repeat_n(&mut self, times: usize) -> Output;
+
+    fn repeat<const N: usize>(&mut self) -> Output {
+        self.repeat_n(N)
+    }
 }
 
 impl<F, Output> RepeatOneshot0<Output> for F
 where
     F: FnMut() -> Output,
 {
-    fn repeat<const N: usize>(&mut self) -> Output {
-        let mut repeat = N;
-        loop {
-            let res = self();
-            repeat -= 1;
-            if repeat == 0 {
-                break res;
-            }
-        }
+    fn repeat_n(&mut self, times: usize) -> Output {
+        Repeat::repeat_n(self, times, ())
     }
 }
 
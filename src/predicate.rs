@@ -0,0 +1,29 @@
+//! Deciding which failures are worth retrying.
+//!
+//! By default every failure is retried. A predicate set with `.retry_if(..)`
+//! sees the error of each failed attempt, when it returns `false` the error is
+//! treated as permanent and handed back without using up the remaining
+//! attempts.
+
+pub trait RetryIf<E> {
+    fn should_retry(&mut self, error: &E) -> bool;
+}
+
+impl<E, F> RetryIf<E> for F
+where
+    F: FnMut(&E) -> bool,
+{
+    fn should_retry(&mut self, error: &E) -> bool {
+        self(error)
+    }
+}
+
+/// Retry every failure
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Always;
+
+impl<E> RetryIf<E> for Always {
+    fn should_retry(&mut self, _: &E) -> bool {
+        true
+    }
+}
@@ -0,0 +1,45 @@
+//! The pseudo-random numbers behind jitter and adaptive throttling.
+
+use core::time::Duration;
+use std::hash::{BuildHasher, Hasher, RandomState};
+
+/// SplitMix64, good enough to spread out retries without pulling in `rand`
+#[derive(Debug, Clone)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Seeded from the random keys of the std hash maps
+    pub(crate) fn random() -> Self {
+        Self(RandomState::new().build_hasher().finish())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number uniformly drawn from `[0, 1)`
+    pub(crate) fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A uniformly distributed duration in `low..=high`
+    pub(crate) fn between(&mut self, low: Duration, high: Duration) -> Duration {
+        // Scale a random u64 onto the range, split in two so it can't overflow
+        let span = high.saturating_sub(low).as_nanos() + 1;
+        let random = u128::from(self.next_u64());
+        let nanos = (span >> 64) * random + (((span & u128::from(u64::MAX)) * random) >> 64);
+        let offset = Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        );
+        low.saturating_add(offset)
+    }
+}
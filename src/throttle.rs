@@ -0,0 +1,197 @@
+//! Shedding load on the client while a backend is overloaded.
+//!
+//! An [`AdaptiveThrottle`] implements the client-side throttling of the
+//! Google SRE book. It counts the requests made over a sliding window and the
+//! ones the backend accepted, classified with [`Tryable`], and rejects new
+//! requests locally with probability
+//! `max(0, (requests - K * accepts) / (requests + 1))`. While the backend
+//! accepts everything nothing is rejected. Once it starts failing, the
+//! requests that still leave the process are roughly `K` times the ones it
+//! can handle.
+//!
+//! The throttle is shared between retriers behind an `Arc` and attached with
+//! `.throttle(..)` on the builder and async retriers. A rejected attempt fails
+//! with a [`Throttled`](crate::error::Throttled) error, see [`FromThrottled`].
+//!
+//! ```rust
+//! use core::time::Duration;
+//! use retry::throttle::AdaptiveThrottle;
+//!
+//! let throttle = AdaptiveThrottle::new(2.0, Duration::from_secs(60));
+//! for _ in 0..10 {
+//!     assert!(throttle.allow());
+//!     throttle.record(&Ok::<_, ()>(()));
+//! }
+//! assert_eq!(throttle.rejection_probability(), 0.0);
+//!
+//! for _ in 0..50 {
+//!     throttle.allow();
+//!     throttle.record(&Err::<(), _>("overloaded"));
+//! }
+//! assert!(throttle.rejection_probability() > 0.5);
+//! ```
+
+use core::time::Duration;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use crate::circuit::Breaker;
+use crate::error::{FromThrottled, Stop};
+use crate::rng::Rng;
+use crate::tryable::Tryable;
+
+/// The number of buckets the window is split into
+const BUCKETS: u32 = 16;
+
+#[derive(Debug)]
+struct Bucket {
+    start: Instant,
+    requests: u64,
+    accepts: u64,
+}
+
+#[derive(Debug)]
+struct Inner {
+    /// Counts of the window, oldest first
+    buckets: VecDeque<Bucket>,
+    /// Draws the rejections
+    rng: Rng,
+}
+
+impl Inner {
+    /// The bucket counting calls made now, dropping the ones out of the
+    /// window
+    fn current(&mut self, window: Duration) -> &mut Bucket {
+        let now = Instant::now();
+        while let Some(bucket) = self.buckets.front() {
+            if now.duration_since(bucket.start) < window {
+                break;
+            }
+            self.buckets.pop_front();
+        }
+        let fresh = match self.buckets.back() {
+            Some(bucket) => now.duration_since(bucket.start) >= window / BUCKETS,
+            None => true,
+        };
+        if fresh {
+            self.buckets.push_back(Bucket {
+                start: now,
+                requests: 0,
+                accepts: 0,
+            });
+        }
+        self.buckets.back_mut().expect("a bucket was just pushed")
+    }
+
+    /// The requests and accepts over the window
+    fn totals(&self) -> (u64, u64) {
+        self.buckets
+            .iter()
+            .fold((0, 0), |(requests, accepts), bucket| {
+                (requests + bucket.requests, accepts + bucket.accepts)
+            })
+    }
+}
+
+/// An adaptive throttle shared between retriers, see the
+/// [module docs](self)
+#[derive(Debug)]
+pub struct AdaptiveThrottle {
+    k: f64,
+    window: Duration,
+    inner: Mutex<Inner>,
+}
+
+impl AdaptiveThrottle {
+    /// Let up to `k` times the accepted requests of the last `window`
+    /// through
+    ///
+    /// Lower multipliers shed load more aggressively, the SRE book suggests
+    /// 2.
+    pub fn new(k: f64, window: Duration) -> Self {
+        AdaptiveThrottle {
+            k,
+            window,
+            inner: Mutex::new(Inner {
+                buckets: VecDeque::new(),
+                rng: Rng::random(),
+            }),
+        }
+    }
+
+    /// Draw the rejections from a generator seeded with `seed`, for
+    /// reproducible runs
+    pub fn seed(mut self, seed: u64) -> Self {
+        let inner = self.inner.get_mut().unwrap_or_else(PoisonError::into_inner);
+        inner.rng = Rng::new(seed);
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn probability(&self, requests: u64, accepts: u64) -> f64 {
+        let requests = requests as f64;
+        ((requests - self.k * accepts as f64) / (requests + 1.0)).max(0.0)
+    }
+
+    /// The probability with which the next request is rejected
+    pub fn rejection_probability(&self) -> f64 {
+        let mut inner = self.lock();
+        inner.current(self.window);
+        let (requests, accepts) = inner.totals();
+        self.probability(requests, accepts)
+    }
+
+    /// Whether a request may go through now
+    ///
+    /// The request counts towards the window even when it's rejected.
+    pub fn allow(&self) -> bool {
+        let mut inner = self.lock();
+        inner.current(self.window).requests += 1;
+        let (requests, accepts) = inner.totals();
+        let p = self.probability(requests - 1, accepts);
+        p == 0.0 || inner.rng.unit() >= p
+    }
+
+    /// Record the outcome of a request, a success counts as accepted
+    pub fn record<T: Tryable>(&self, output: &T) {
+        if !output.negative() {
+            self.record_accept()
+        }
+    }
+
+    /// Record a request the backend accepted
+    pub fn record_accept(&self) {
+        self.lock().current(self.window).accepts += 1;
+    }
+}
+
+/// A circuit breaker, or [`NoBreaker`](crate::circuit::NoBreaker), consulted
+/// before a throttle
+pub type WithThrottle<C> = (C, Arc<AdaptiveThrottle>);
+
+impl Default for AdaptiveThrottle {
+    /// `K = 2` over the last 2 minutes
+    fn default() -> Self {
+        Self::new(2.0, Duration::from_secs(120))
+    }
+}
+
+impl<Out> Breaker<Out> for Arc<AdaptiveThrottle>
+where
+    Out: Tryable + FromThrottled,
+{
+    fn admit(&self, attempts: usize) -> Result<(), (Out, Stop)> {
+        if self.allow() {
+            Ok(())
+        } else {
+            Err((Out::throttled(), Stop::Throttled { attempts }))
+        }
+    }
+    fn record(&self, output: &Out) {
+        AdaptiveThrottle::record(self, output)
+    }
+}
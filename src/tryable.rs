@@ -1,7 +1,49 @@
-pub trait Tryable: seal::Sealed {
+//! Telling successful attempts from failed ones.
+//!
+//! The retriers work with any output implementing [`Tryable`]. It comes
+//! implemented for `Result`, `Option`, `bool`, [`ControlFlow`] and
+//! [`ExitStatus`], and can be implemented for your own types:
+//!
+//! ```rust
+//! use retry::Tryable;
+//!
+//! struct Response {
+//!     status: u16,
+//! }
+//!
+//! impl Tryable for Response {
+//!     type Ok = Response;
+//!     type Error = Response;
+//!     fn error(&self) -> Option<&Response> {
+//!         (self.status >= 500).then_some(self)
+//!     }
+//!     fn into_result(self) -> Result<Response, Response> {
+//!         if self.status >= 500 {
+//!             Err(self)
+//!         } else {
+//!             Ok(self)
+//!         }
+//!     }
+//! }
+//! ```
+
+use core::ops::ControlFlow;
+use std::process::ExitStatus;
+
+pub trait Tryable {
     type Ok;
     type Error;
-    fn negative(&self) -> bool;
+
+    /// Whether the attempt failed
+    fn negative(&self) -> bool {
+        self.error().is_some()
+    }
+
+    /// The error of a failed attempt, `None` on success
+    fn error(&self) -> Option<&Self::Error>;
+
+    /// Hand back the success or error value
+    fn into_result(self) -> Result<Self::Ok, Self::Error>;
 }
 
 impl<Ok, Error> Tryable for Result<Ok, Error> {
@@ -10,6 +52,12 @@ impl<Ok, Error> Tryable for Result<Ok, Error> {
     fn negative(&self) -> bool {
         self.is_err()
     }
+    fn error(&self) -> Option<&Error> {
+        self.as_ref().err()
+    }
+    fn into_result(self) -> Result<Ok, Error> {
+        self
+    }
 }
 
 impl<T> Tryable for Option<T> {
@@ -18,10 +66,70 @@ impl<T> Tryable for Option<T> {
     fn negative(&self) -> bool {
         self.is_none()
     }
+    fn error(&self) -> Option<&()> {
+        self.is_none().then_some(&())
+    }
+    fn into_result(self) -> Result<T, ()> {
+        self.ok_or(())
+    }
+}
+
+/// `false` is a failure
+impl Tryable for bool {
+    type Ok = ();
+    type Error = ();
+    fn negative(&self) -> bool {
+        !*self
+    }
+    fn error(&self) -> Option<&()> {
+        (!*self).then_some(&())
+    }
+    fn into_result(self) -> Result<(), ()> {
+        if self {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Like `?`, `Continue` carries on with its value and `Break` is a failure,
+/// retried with its value as the error
+impl<B, C> Tryable for ControlFlow<B, C> {
+    type Ok = C;
+    type Error = B;
+    fn negative(&self) -> bool {
+        self.is_break()
+    }
+    fn error(&self) -> Option<&B> {
+        match self {
+            ControlFlow::Break(b) => Some(b),
+            ControlFlow::Continue(_) => None,
+        }
+    }
+    fn into_result(self) -> Result<C, B> {
+        match self {
+            ControlFlow::Continue(c) => Ok(c),
+            ControlFlow::Break(b) => Err(b),
+        }
+    }
 }
 
-mod seal {
-    pub trait Sealed {}
-    impl<T> Sealed for Option<T> {}
-    impl<T, E> Sealed for Result<T, E> {}
+/// Any unsuccessful exit is a failure
+impl Tryable for ExitStatus {
+    type Ok = ExitStatus;
+    type Error = ExitStatus;
+    fn negative(&self) -> bool {
+        !self.success()
+    }
+    fn error(&self) -> Option<&ExitStatus> {
+        (!self.success()).then_some(self)
+    }
+    fn into_result(self) -> Result<ExitStatus, ExitStatus> {
+        if self.success() {
+            Ok(self)
+        } else {
+            Err(self)
+        }
+    }
 }
@@ -0,0 +1,317 @@
+//! Retrying functions with their arguments packed in a tuple.
+//!
+//! The traits in [`oneshot`](crate::oneshot), which the
+//! [`prelude`](crate::prelude) exports, and in `future` come in one version
+//! per number of arguments, up to 10. The traits here cover functions of up
+//! to 12 arguments through [`TupleCall`], at the cost of wrapping the
+//! arguments in a tuple (`(a,)` for a single one, `()` for none).
+//!
+//! ```rust
+//! use retry::tuple::*;
+//!
+//! fn add(a: i32, b: i32) -> Result<i32, ()> {
+//!     Ok(a + b)
+//! }
+//!
+//! assert_eq!(add.retry::<3>((1, 2)), Ok(3));
+//! ```
+//!
+//! Tuples of arguments are cloned for every attempt. To retry arguments that
+//! aren't `Clone`, or are too expensive to copy, either pass them by reference
+//! (references are `Copy`), or build them for each attempt with [`args_fn`]:
+//!
+//! ```rust
+//! use retry::tuple::*;
+//!
+//! struct Connection;
+//!
+//! fn send(_: &Connection, body: Vec<u8>) -> Result<usize, ()> {
+//!     Ok(body.len())
+//! }
+//!
+//! let connection = Connection;
+//! assert_eq!(send.retry::<3>((&connection, vec![0; 4])), Ok(4));
+//! assert_eq!(
+//!     send.retry::<3>(args_fn(|attempt| (&connection, vec![0; attempt]))),
+//!     Ok(1),
+//! );
+//! ```
+
+use crate::attempt::Attempts;
+use crate::error::{Outcome, RetryError, Stop, WithRetryError};
+use crate::tryable::Tryable;
+
+/// A function called with its arguments packed in a tuple
+pub trait TupleCall<Args> {
+    type Output;
+    fn call_tuple(&mut self, args: Args) -> Self::Output;
+}
+
+macro_rules! impl_tuple_call {
+    ($($item: ident),*) => {
+        #[allow(non_snake_case)]
+        impl<F, O, $($item),*> TupleCall<($($item,)*)> for F
+        where
+            F: FnMut($($item),*) -> O,
+        {
+            type Output = O;
+            fn call_tuple(&mut self, ($($item,)*): ($($item,)*)) -> O {
+                self($($item),*)
+            }
+        }
+    };
+}
+
+impl_tuple_call!();
+impl_tuple_call!(A1);
+impl_tuple_call!(A1, A2);
+impl_tuple_call!(A1, A2, A3);
+impl_tuple_call!(A1, A2, A3, A4);
+impl_tuple_call!(A1, A2, A3, A4, A5);
+impl_tuple_call!(A1, A2, A3, A4, A5, A6);
+impl_tuple_call!(A1, A2, A3, A4, A5, A6, A7);
+impl_tuple_call!(A1, A2, A3, A4, A5, A6, A7, A8);
+impl_tuple_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_tuple_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_tuple_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_tuple_call!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
+
+/// Where a retrier gets the arguments of each attempt from
+pub trait ArgSource {
+    type Args;
+    /// The arguments for attempt number `attempt`, counted from 1
+    fn args(&mut self, attempt: usize) -> Self::Args;
+}
+
+macro_rules! impl_arg_source {
+    ($($item: ident),*) => {
+        impl<$($item: Clone),*> ArgSource for ($($item,)*) {
+            type Args = Self;
+            fn args(&mut self, _: usize) -> Self {
+                self.clone()
+            }
+        }
+    };
+}
+
+impl_arg_source!();
+impl_arg_source!(A1);
+impl_arg_source!(A1, A2);
+impl_arg_source!(A1, A2, A3);
+impl_arg_source!(A1, A2, A3, A4);
+impl_arg_source!(A1, A2, A3, A4, A5);
+impl_arg_source!(A1, A2, A3, A4, A5, A6);
+impl_arg_source!(A1, A2, A3, A4, A5, A6, A7);
+impl_arg_source!(A1, A2, A3, A4, A5, A6, A7, A8);
+impl_arg_source!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_arg_source!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_arg_source!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_arg_source!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
+
+/// Arguments built for each attempt, see [`args_fn`]
+#[derive(Debug, Clone, Copy)]
+pub struct ArgsFn<G>(G);
+
+/// Build the arguments of each attempt by calling `make` with the attempt
+/// number, instead of cloning them
+pub fn args_fn<G, Args>(make: G) -> ArgsFn<G>
+where
+    G: FnMut(usize) -> Args,
+{
+    ArgsFn(make)
+}
+
+impl<G, Args> ArgSource for ArgsFn<G>
+where
+    G: FnMut(usize) -> Args,
+{
+    type Args = Args;
+    fn args(&mut self, attempt: usize) -> Args {
+        (self.0)(attempt)
+    }
+}
+
+/// Why to stop once attempt number `attempts` returned `result`, `None` to
+/// try again
+pub(crate) fn stop<Output: Tryable>(
+    result: &Output,
+    attempts: usize,
+    limit: Attempts,
+) -> Option<Stop> {
+    if !result.negative() {
+        Some(Stop::Success)
+    } else if attempts > limit.retries {
+        Some(Stop::Exhausted { attempts })
+    } else {
+        None
+    }
+}
+
+/// Call `attempt` with the attempt number until it succeeds or runs out of
+/// attempts
+fn retry<Output: Tryable>(
+    limit: Attempts,
+    mut attempt: impl FnMut(usize) -> Output,
+) -> (Output, Stop) {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let result = attempt(attempts);
+        if let Some(stop) = stop(&result, attempts, limit) {
+            return (result, stop);
+        }
+    }
+}
+
+pub trait Retry<Args: ArgSource>: TupleCall<Args::Args> + Sized
+where
+    Self::Output: Tryable,
+{
+    /// Retry with a count read at runtime, see [`Attempts`]
+    fn retry_n(self, attempts: impl Into<Attempts>, args: Args) -> Self::Output;
+    fn try_retry_n(
+        self,
+        attempts: impl Into<Attempts>,
+        args: Args,
+    ) -> Result<<Self::Output as Tryable>::Ok, RetryError<<Self::Output as Tryable>::Error>>;
+
+    fn retry<const N: usize>(self, args: Args) -> Self::Output {
+        self.retry_n(N, args)
+    }
+    fn try_retry<const N: usize>(
+        self,
+        args: Args,
+    ) -> Result<<Self::Output as Tryable>::Ok, RetryError<<Self::Output as Tryable>::Error>> {
+        self.try_retry_n(N, args)
+    }
+}
+
+impl<F, Args: ArgSource> Retry<Args> for F
+where
+    F: TupleCall<Args::Args>,
+    F::Output: Tryable,
+{
+    fn retry_n(mut self, attempts: impl Into<Attempts>, mut args: Args) -> F::Output {
+        retry(attempts.into(), |n| self.call_tuple(args.args(n))).0
+    }
+    fn try_retry_n(
+        mut self,
+        attempts: impl Into<Attempts>,
+        mut args: Args,
+    ) -> Result<<F::Output as Tryable>::Ok, RetryError<<F::Output as Tryable>::Error>> {
+        let (output, stop) = retry(attempts.into(), |n| self.call_tuple(args.args(n)));
+        WithRetryError::report(output, stop)
+    }
+}
+
+pub trait Repeat<Args: ArgSource>: TupleCall<Args::Args> + Sized {
+    /// Call the function `times` times, read at runtime, and at least once
+    fn repeat_n(&mut self, times: usize, args: Args) -> Self::Output;
+
+    fn repeat<const N: usize>(&mut self, args: Args) -> Self::Output {
+        self.repeat_n(N, args)
+    }
+}
+
+impl<F, Args: ArgSource> Repeat<Args> for F
+where
+    F: TupleCall<Args::Args>,
+{
+    fn repeat_n(&mut self, times: usize, mut args: Args) -> F::Output {
+        for n in 1..times {
+            self.call_tuple(args.args(n));
+        }
+        self.call_tuple(args.args(times.max(1)))
+    }
+}
+
+/// Run a builder with its arguments packed in a tuple
+///
+/// Implemented for `&mut` the sync builders, which can be run again, and for
+/// the async builders, which turn into the future doing the retries. `Out` is
+/// what the function returns.
+#[cfg(feature = "builder")]
+pub trait Run<Args, Out> {
+    type Output;
+    fn run(self, args: Args) -> Self::Output;
+}
+
+#[cfg(feature = "futures")]
+pub use self::future::*;
+
+#[cfg(feature = "futures")]
+mod future {
+    use core::future::Future;
+    use core::time::Duration;
+
+    use super::{ArgSource, TupleCall};
+    use crate::attempt::Attempts;
+    use crate::call::Call;
+    use crate::future::{hedge::Hedger, repeat::Repeater, retry::Retrier};
+    use crate::tryable::Tryable;
+
+    pub trait AsyncRetry<Args, Fut: Future>: Sized {
+        /// Retry with a count read at runtime, see [`Attempts`]
+        fn retry_n(self, attempts: impl Into<Attempts>, args: Args) -> Retrier<Self, Args, Fut> {
+            Retrier::new(attempts.into(), self, args)
+        }
+
+        fn retry<const N: usize>(self, args: Args) -> Retrier<Self, Args, Fut> {
+            self.retry_n(N, args)
+        }
+    }
+
+    impl<F, Args, Fut> AsyncRetry<Args, Fut> for F
+    where
+        Args: ArgSource,
+        F: Call<Args::Args, <Fut::Output as Tryable>::Error, Output = Fut>,
+        Fut: Future,
+        Fut::Output: Tryable,
+    {
+    }
+
+    pub trait AsyncRepeat<Args, Fut>: Sized {
+        /// Repeat `times` times, read at runtime, and at least once
+        fn repeat_n(self, times: usize, args: Args) -> Repeater<Self, Args, Fut> {
+            Repeater::new(times, self, args)
+        }
+
+        fn repeat<const N: usize>(self, args: Args) -> Repeater<Self, Args, Fut> {
+            self.repeat_n(N, args)
+        }
+    }
+
+    impl<F, Args, Fut> AsyncRepeat<Args, Fut> for F
+    where
+        Args: ArgSource,
+        F: TupleCall<Args::Args, Output = Fut>,
+        Fut: Future,
+    {
+    }
+
+    pub trait AsyncHedge<Args, Fut: Future>: Sized {
+        /// Run up to `attempts` attempts, read at runtime, starting a new one
+        /// every `stagger`
+        fn hedge_n(
+            self,
+            attempts: usize,
+            stagger: Duration,
+            args: Args,
+        ) -> Hedger<Self, Args, Fut> {
+            Hedger::new(attempts, stagger, self, args)
+        }
+
+        fn hedge<const N: usize>(self, stagger: Duration, args: Args) -> Hedger<Self, Args, Fut> {
+            self.hedge_n(N, stagger, args)
+        }
+    }
+
+    impl<F, Args, Fut> AsyncHedge<Args, Fut> for F
+    where
+        Args: ArgSource,
+        F: TupleCall<Args::Args, Output = Fut>,
+        Fut: Future,
+        Fut::Output: Tryable,
+    {
+    }
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "futures")]
+
+use core::time::Duration;
+use std::time::Instant;
+
+use retry::backoff::Constant;
+use retry::future::async_fn::*;
+use retry::tuple::args_fn;
+
+#[tokio::test]
+async fn borrows_captured_state_across_await() {
+    let mut log: Vec<String> = Vec::new();
+    let flaky = async |name: &str| {
+        log.push(name.to_string());
+        let entry = log.last_mut().unwrap();
+        tokio::task::yield_now().await;
+        entry.push('!');
+        if log.len() < 3 {
+            Err(log.len())
+        } else {
+            Ok(log.join(" "))
+        }
+    };
+    let name = String::from("hello");
+    let result = flaky.retry::<3>((name.as_str(),)).await;
+    assert_eq!(result.as_deref(), Ok("hello! hello! hello!"));
+    assert_eq!(log.len(), 3);
+}
+
+#[tokio::test]
+async fn gives_up_after_the_attempts() {
+    let mut calls = 0;
+    let result = (async || {
+        calls += 1;
+        tokio::task::yield_now().await;
+        Err::<(), _>(calls)
+    })
+    .retry_n(2, ())
+    .await;
+    assert_eq!(result, Err(3));
+}
+
+#[tokio::test]
+async fn repeats_with_built_arguments() {
+    let mut seen = Vec::new();
+    let last = (async |a: usize, b: usize| {
+        tokio::task::yield_now().await;
+        seen.push(a + b);
+        a * b
+    })
+    .repeat::<3>(args_fn(|attempt| (attempt, 10)))
+    .await;
+    assert_eq!(last, 30);
+    assert_eq!(seen, [11, 12, 13]);
+}
+
+struct Client {
+    requests: usize,
+}
+
+impl Client {
+    /// Fails with the number of requests until the third one
+    async fn fetch(&mut self) -> Result<usize, usize> {
+        self.requests += 1;
+        tokio::task::yield_now().await;
+        if self.requests < 3 {
+            Err(self.requests)
+        } else {
+            Ok(self.requests)
+        }
+    }
+}
+
+#[tokio::test]
+async fn retry_with_reborrows_the_receiver() {
+    let mut client = Client { requests: 0 };
+    let result = retry_with(&mut client, 3, async |c| c.fetch().await)
+        .run()
+        .await;
+    assert_eq!(result, Ok(3));
+    assert_eq!(client.requests, 3);
+
+    let mut client = Client { requests: 0 };
+    let result = retry_with(&mut client, 1, async |c| c.fetch().await)
+        .run()
+        .await;
+    assert_eq!(result, Err(2));
+    assert_eq!(client.requests, 2);
+}
+
+#[tokio::test]
+async fn retry_with_waits_and_filters() {
+    let mut client = Client { requests: 0 };
+    let started = Instant::now();
+    let result = retry_with(&mut client, 5, async |c| c.fetch().await)
+        .backoff(Constant::new(Duration::from_millis(20)))
+        .sleeper(tokio::time::sleep)
+        .run()
+        .await;
+    assert_eq!(result, Ok(3));
+    assert_eq!(client.requests, 3);
+    assert!(started.elapsed() >= Duration::from_millis(40));
+
+    let mut client = Client { requests: 0 };
+    let result = retry_with(&mut client, 5, async |c| c.fetch().await)
+        .retry_if(|requests: &usize| *requests < 2)
+        .run()
+        .await;
+    assert_eq!(result, Err(2));
+    assert_eq!(client.requests, 2);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::failing;
+#[cfg(feature = "futures")]
+use common::ready;
+use retry::Attempts;
+
+#[test]
+fn counts_agree() {
+    assert_eq!(Attempts::from(3), Attempts::retries(3));
+    assert_eq!(Attempts::retries(3), Attempts::total(4));
+    assert_eq!(Attempts::total(0), Attempts::total(1));
+    assert_eq!(Attempts::total(1), Attempts::retries(0));
+}
+
+#[test]
+fn oneshot_counts() {
+    use retry::prelude::*;
+
+    for (attempts, calls) in [
+        (Attempts::retries(0), 1),
+        (Attempts::retries(3), 4),
+        (Attempts::total(0), 1),
+        (Attempts::total(3), 3),
+    ] {
+        assert_eq!(failing().retry_n(attempts), Err(calls));
+    }
+
+    assert_eq!(failing().retry::<0>(), Err(1));
+}
+
+#[test]
+fn oneshot_repeat_zero_calls_once() {
+    use retry::prelude::*;
+
+    assert_eq!(failing().repeat_n(0), Err(1));
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn future_counts() {
+    use retry::future::repeat::*;
+    use retry::future::retry::*;
+
+    for (attempts, calls) in [(Attempts::retries(0), 1), (Attempts::total(3), 3)] {
+        assert_eq!(ready(failing()).retry_n(attempts).await, Err(calls));
+    }
+
+    assert_eq!(ready(failing()).repeat_n(0).await, Err(1));
+}
+
+#[cfg(feature = "builder")]
+#[test]
+fn builder_counts() {
+    use retry::builder::{repeat::*, retry::*, run::*};
+
+    for (attempts, calls) in [
+        (Attempts::retries(0), 1),
+        (Attempts::retries(3), 4),
+        (Attempts::total(0), 1),
+        (Attempts::total(3), 3),
+    ] {
+        assert_eq!(failing().retry(attempts).run(), Err(calls));
+    }
+
+    assert_eq!(failing().retry(0).run(), Err(1));
+    assert_eq!(failing().repeat(0).run(), Err(1));
+}
+
+#[cfg(all(feature = "builder", feature = "futures"))]
+#[tokio::test]
+async fn async_builder_counts() {
+    use retry::builder::future::*;
+
+    assert_eq!(ready(failing()).retry(0).run().await, Err(1));
+    assert_eq!(
+        ready(failing()).retry(Attempts::total(3)).run().await,
+        Err(3)
+    );
+    assert_eq!(ready(failing()).repeat(0).run().await, Err(1));
+}
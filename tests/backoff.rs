@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use retry::backoff::{Constant, Exponential, Fibonacci, Linear};
+
+fn ms(delays: impl IntoIterator<Item = Duration>) -> Vec<u128> {
+    delays.into_iter().map(|delay| delay.as_millis()).collect()
+}
+
+#[test]
+fn sequences() {
+    let constant = Constant::new(Duration::from_millis(50)).take(3);
+    assert_eq!(ms(constant), [50, 50, 50]);
+    let linear = Linear::new(Duration::from_millis(100), Duration::from_millis(50)).take(4);
+    assert_eq!(ms(linear), [100, 150, 200, 250]);
+    let exponential = Exponential::new(Duration::from_millis(10))
+        .factor(3)
+        .take(4);
+    assert_eq!(ms(exponential), [10, 30, 90, 270]);
+    let fibonacci = Fibonacci::new(Duration::from_millis(10)).take(7);
+    assert_eq!(ms(fibonacci), [10, 10, 20, 30, 50, 80, 130]);
+}
+
+#[test]
+fn max_delay_caps_every_strategy() {
+    let max = Duration::from_millis(100);
+    let constant = Constant::new(Duration::from_millis(150))
+        .max_delay(max)
+        .take(2);
+    assert_eq!(ms(constant), [100, 100]);
+    let linear = Linear::new(Duration::from_millis(40), Duration::from_millis(30));
+    assert_eq!(ms(linear.max_delay(max).take(4)), [40, 70, 100, 100]);
+    let exponential = Exponential::new(Duration::from_millis(30)).max_delay(max);
+    assert_eq!(ms(exponential.take(4)), [30, 60, 100, 100]);
+    let fibonacci = Fibonacci::new(Duration::from_millis(30)).max_delay(max);
+    assert_eq!(ms(fibonacci.take(5)), [30, 30, 60, 90, 100]);
+}
+
+#[test]
+fn delays_saturate_near_the_maximum() {
+    let half = Duration::MAX / 2;
+    let linear = Linear::new(half, half).take(4);
+    assert_eq!(linear.last(), Some(Duration::MAX));
+    let exponential = Exponential::new(half).take(4);
+    assert_eq!(exponential.last(), Some(Duration::MAX));
+    let fibonacci = Fibonacci::new(half).take(5);
+    assert_eq!(fibonacci.last(), Some(Duration::MAX));
+    // A cap still applies once the delays saturate
+    let capped = Exponential::new(half)
+        .max_delay(Duration::from_secs(1))
+        .take(4);
+    assert_eq!(capped.last(), Some(Duration::from_secs(1)));
+}
+
+fn exponential() -> Exponential {
+    Exponential::new(Duration::from_millis(100)).max_delay(Duration::from_secs(2))
+}
+
+#[test]
+fn seeded_jitter_is_deterministic() {
+    let first: Vec<_> = exponential().full_jitter().seed(7).take(8).collect();
+    let second: Vec<_> = exponential().full_jitter().seed(7).take(8).collect();
+    let other: Vec<_> = exponential().full_jitter().seed(8).take(8).collect();
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+}
+
+#[test]
+fn jitter_stays_within_bounds() {
+    let caps: Vec<_> = exponential().take(64).collect();
+
+    let full = exponential().full_jitter().seed(1).take(64);
+    for (delay, cap) in full.zip(&caps) {
+        assert!(delay <= *cap);
+    }
+
+    let equal = exponential().equal_jitter().seed(2).take(64);
+    for (delay, cap) in equal.zip(&caps) {
+        assert!(delay >= *cap / 2 && delay <= *cap);
+    }
+
+    let decorrelated = exponential().decorrelated_jitter().seed(3).take(64);
+    for delay in decorrelated {
+        assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_secs(2));
+    }
+}
+
+#[test]
+fn jitter_handles_saturated_delays() {
+    let delays = Exponential::new(Duration::MAX)
+        .full_jitter()
+        .seed(4)
+        .take(4);
+    assert_eq!(delays.count(), 4);
+}
@@ -0,0 +1,63 @@
+use retry::budget::RetryBudget;
+
+#[test]
+fn deposits_round_the_ratio() {
+    // The closest f32 to 0.7 is just below it
+    let budget = RetryBudget::new(10, 0.7);
+    while budget.withdraw() {}
+    for _ in 0..10 {
+        budget.deposit();
+    }
+    assert_eq!(budget.tokens(), 7.0);
+}
+
+#[test]
+fn deposits_stop_at_the_max() {
+    let budget = RetryBudget::new(2, 0.5);
+    for _ in 0..10 {
+        budget.deposit();
+    }
+    assert_eq!(budget.tokens(), 2.0);
+    assert!(RetryBudget::new(1, -1.0).withdraw());
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn async_retries_stop_once_the_budget_runs_dry() {
+    use retry::future::retry::*;
+    use retry::RetryError;
+    use std::sync::Arc;
+
+    let budget = Arc::new(RetryBudget::new(2, 0.1));
+    let mut calls = 0;
+    let result = (|| {
+        calls += 1;
+        std::future::ready(Err::<(), _>(calls))
+    })
+    .retry::<5>()
+    .budget(budget.clone())
+    .with_retry_error()
+    .await;
+    assert_eq!(
+        result,
+        Err(RetryError::Throttled {
+            last: 3,
+            attempts: 3
+        })
+    );
+    assert_eq!(budget.tokens(), 0.0);
+
+    // A dry budget leaves only the first attempt
+    let result = (|| std::future::ready(Err::<(), _>("unavailable")))
+        .retry::<5>()
+        .budget(budget)
+        .with_retry_error()
+        .await;
+    assert_eq!(
+        result,
+        Err(RetryError::Throttled {
+            last: "unavailable",
+            attempts: 1
+        })
+    );
+}
@@ -0,0 +1,125 @@
+#[cfg(any(feature = "builder", feature = "futures"))]
+mod common;
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+use core::time::Duration;
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+use common::failing;
+#[cfg(feature = "futures")]
+use common::ready;
+#[cfg(any(feature = "builder", feature = "futures"))]
+use retry::cancel::CancelToken;
+use retry::RetryError;
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+const DELAY: Duration = Duration::from_millis(20);
+
+/// [`failing`], cancelling `token` on call `cancel_at`
+#[cfg(any(feature = "builder", feature = "futures"))]
+fn cancelling(token: CancelToken, cancel_at: usize) -> impl FnMut() -> Result<usize, usize> {
+    let mut failing = failing();
+    move || {
+        let result = failing();
+        if result == Err(cancel_at) {
+            token.cancel();
+        }
+        result
+    }
+}
+
+#[cfg(feature = "builder")]
+#[test]
+fn sync_stops_before_the_next_retry() {
+    use retry::backoff::Constant;
+    use retry::builder::{retry::*, run::*};
+
+    let token = CancelToken::new();
+    let result = cancelling(token.clone(), 2)
+        .retry(5)
+        .cancel_on(token)
+        .with_retry_error()
+        .run();
+    assert_eq!(result, Err(RetryError::Cancelled));
+
+    // Cancelled from another thread while sleeping
+    let token = CancelToken::new();
+    let handle = token.clone();
+    let canceller = std::thread::spawn(move || {
+        std::thread::sleep(DELAY / 2);
+        handle.cancel();
+    });
+    let mut exhausted = None;
+    let result = failing()
+        .retry(5)
+        .backoff(Constant::new(DELAY))
+        .cancel_on(token)
+        .on_exhausted(|e: &usize, attempts| exhausted = Some((*e, attempts)))
+        .run();
+    canceller.join().unwrap();
+    assert_eq!(result, Err(1));
+    assert_eq!(exhausted, Some((1, 1)));
+}
+
+#[cfg(feature = "builder")]
+#[test]
+fn sync_makes_the_first_attempt() {
+    use retry::builder::{retry::*, run::*};
+
+    let token = CancelToken::new();
+    token.cancel();
+    let result = failing().retry(5).cancel_on(token).run();
+    assert_eq!(result, Err(1));
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn async_stops_before_the_next_retry() {
+    use retry::backoff::Constant;
+    use retry::future::retry::*;
+
+    let token = CancelToken::new();
+    let result = ready(cancelling(token.clone(), 3))
+        .retry::<5>()
+        .cancel_on(token)
+        .with_retry_error()
+        .await;
+    assert_eq!(result, Err(RetryError::Cancelled));
+
+    // Cancelled while sleeping, stopping once the sleep is over
+    let token = CancelToken::new();
+    let handle = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(DELAY / 2).await;
+        handle.cancel();
+    });
+    let result = ready(failing())
+        .retry::<5>()
+        .backoff(Constant::new(DELAY))
+        .sleeper(tokio::time::sleep)
+        .cancel_on(token)
+        .await;
+    assert_eq!(result, Err(1));
+}
+
+#[cfg(all(feature = "builder", feature = "futures"))]
+#[tokio::test]
+async fn async_builder_stops_once_cancelled() {
+    use retry::builder::future::*;
+
+    let token = CancelToken::new();
+    let result = ready(cancelling(token.clone(), 2))
+        .retry(5)
+        .cancel_on(token)
+        .with_retry_error()
+        .run()
+        .await;
+    assert_eq!(result, Err(RetryError::Cancelled));
+}
+
+#[test]
+fn displays_and_has_no_inner_error() {
+    let error = RetryError::<String>::Cancelled;
+    assert_eq!(error.to_string(), "cancelled");
+    assert_eq!(error.into_inner(), None);
+}
@@ -0,0 +1,153 @@
+use core::time::Duration;
+
+use retry::circuit::{CircuitBreaker, State};
+
+const COOL_DOWN: Duration = Duration::from_secs(60);
+
+#[test]
+fn zero_failure_rate_trips_on_the_first_failure() {
+    let breaker = CircuitBreaker::failure_rate(0.0, 4, COOL_DOWN);
+    for _ in 0..10 {
+        breaker.record(&Ok::<_, ()>(()));
+    }
+    assert_eq!(breaker.state(), State::Closed);
+    breaker.record(&Err::<(), _>(()));
+    assert_eq!(breaker.state(), State::Open);
+}
+
+#[test]
+fn failure_rate_waits_for_a_full_window() {
+    let breaker = CircuitBreaker::failure_rate(0.5, 4, COOL_DOWN);
+    breaker.record(&Err::<(), _>(()));
+    breaker.record(&Err::<(), _>(()));
+    breaker.record(&Ok::<_, ()>(()));
+    assert_eq!(breaker.state(), State::Closed);
+    breaker.record(&Ok::<_, ()>(()));
+    assert_eq!(breaker.state(), State::Open);
+}
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+#[derive(Debug, PartialEq)]
+enum Error {
+    Unavailable,
+    CircuitOpen,
+}
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+impl From<retry::error::CircuitOpen> for Error {
+    fn from(_: retry::error::CircuitOpen) -> Self {
+        Error::CircuitOpen
+    }
+}
+
+#[cfg(feature = "builder")]
+#[test]
+fn sync_retry_error_carries_the_last_error() {
+    use retry::builder::{retry::*, run::*};
+    use retry::RetryError;
+    use std::sync::Arc;
+
+    let breaker = Arc::new(CircuitBreaker::consecutive_failures(2, COOL_DOWN));
+    let mut retrier = (|| Err::<(), _>(Error::Unavailable))
+        .retry(5)
+        .circuit_breaker(breaker)
+        .with_retry_error();
+    assert_eq!(
+        retrier.run(),
+        Err(RetryError::CircuitOpen {
+            last: Error::Unavailable,
+            attempts: 2
+        })
+    );
+    assert_eq!(
+        retrier.run(),
+        Err(RetryError::CircuitOpen {
+            last: Error::CircuitOpen,
+            attempts: 0
+        })
+    );
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn async_retry_error_carries_the_last_error() {
+    use retry::future::retry::*;
+    use retry::RetryError;
+    use std::sync::Arc;
+
+    let breaker = Arc::new(CircuitBreaker::consecutive_failures(2, COOL_DOWN));
+    let result = (|| async { Err::<(), _>(Error::Unavailable) })
+        .retry::<5>()
+        .circuit_breaker(breaker.clone())
+        .with_retry_error()
+        .await;
+    assert_eq!(
+        result,
+        Err(RetryError::CircuitOpen {
+            last: Error::Unavailable,
+            attempts: 2
+        })
+    );
+    let result = (|| async { Err::<(), _>(Error::Unavailable) })
+        .retry::<5>()
+        .circuit_breaker(breaker)
+        .with_retry_error()
+        .await;
+    assert_eq!(
+        result,
+        Err(RetryError::CircuitOpen {
+            last: Error::CircuitOpen,
+            attempts: 0
+        })
+    );
+}
+
+#[test]
+fn longest_cool_down_stays_open() {
+    let breaker = CircuitBreaker::consecutive_failures(1, Duration::MAX);
+    breaker.record(&Err::<(), _>(()));
+    assert_eq!(breaker.state(), State::Open);
+    assert!(!breaker.allow());
+}
+
+const SHORT: Duration = Duration::from_millis(20);
+
+/// A breaker opened by a single failure and cooled down since
+fn half_open(probes: usize) -> CircuitBreaker {
+    let breaker = CircuitBreaker::consecutive_failures(1, SHORT).half_open_probes(probes);
+    breaker.record(&Err::<(), _>(()));
+    assert!(!breaker.allow());
+    std::thread::sleep(SHORT);
+    assert_eq!(breaker.state(), State::HalfOpen);
+    breaker
+}
+
+#[test]
+fn half_open_admits_only_its_probes() {
+    let breaker = half_open(2);
+    assert!(breaker.allow());
+    assert!(breaker.allow());
+    assert!(!breaker.allow());
+    assert_eq!(breaker.state(), State::HalfOpen);
+}
+
+#[test]
+fn failed_probe_reopens() {
+    let breaker = half_open(2);
+    assert!(breaker.allow());
+    breaker.record(&Err::<(), _>(()));
+    assert_eq!(breaker.state(), State::Open);
+    assert!(!breaker.allow());
+}
+
+#[test]
+fn successful_probes_close() {
+    let breaker = half_open(2);
+    assert!(breaker.allow());
+    breaker.record(&Ok::<_, ()>(()));
+    assert_eq!(breaker.state(), State::HalfOpen);
+    assert!(breaker.allow());
+    breaker.record(&Ok::<_, ()>(()));
+    assert_eq!(breaker.state(), State::Closed);
+    assert!(breaker.allow());
+}
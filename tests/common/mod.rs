@@ -0,0 +1,26 @@
+//! Fixtures shared by the integration tests, not every test uses them all
+#![allow(dead_code)]
+
+/// Fails with the number of the call for the first `fails` calls, then
+/// succeeds with it
+pub fn flaky(fails: usize) -> impl FnMut() -> Result<usize, usize> {
+    let mut calls = 0;
+    move || {
+        calls += 1;
+        if calls > fails {
+            Ok(calls)
+        } else {
+            Err(calls)
+        }
+    }
+}
+
+/// Fails with the number of the call every time
+pub fn failing() -> impl FnMut() -> Result<usize, usize> {
+    flaky(usize::MAX)
+}
+
+/// `f` as an async function whose futures are ready straight away
+pub fn ready<T>(mut f: impl FnMut() -> T) -> impl FnMut() -> std::future::Ready<T> {
+    move || std::future::ready(f())
+}
@@ -0,0 +1,89 @@
+#![cfg(any(feature = "builder", feature = "futures"))]
+
+mod common;
+
+use core::time::Duration;
+use std::time::Instant;
+
+use common::failing;
+#[cfg(feature = "futures")]
+use common::ready;
+use retry::backoff::Constant;
+use retry::RetryError;
+
+const DELAY: Duration = Duration::from_millis(40);
+/// Fits two delays but not a third
+const LIMIT: Duration = Duration::from_millis(100);
+
+#[cfg(feature = "builder")]
+#[test]
+fn sync_stops_before_sleeping_past_the_deadline() {
+    use retry::builder::{retry::*, run::*};
+
+    let deadline = Instant::now() + LIMIT;
+    let mut made = 0;
+    let result = failing()
+        .retry(10)
+        .backoff(Constant::new(DELAY))
+        .deadline(deadline)
+        .on_exhausted(|_: &usize, attempts| made = attempts)
+        .with_retry_error()
+        .run();
+    assert_eq!(result, Err(RetryError::TimedOut));
+    assert_eq!(made, 3);
+    assert!(Instant::now() < deadline);
+
+    // A deadline already past leaves the first attempt only
+    let result = failing()
+        .retry(10)
+        .backoff(Constant::new(DELAY))
+        .deadline(Instant::now())
+        .run();
+    assert_eq!(result, Err(1));
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn async_stops_before_sleeping_past_the_deadline() {
+    use retry::future::retry::*;
+
+    let deadline = Instant::now() + LIMIT;
+    let mut made = 0;
+    let result = ready(failing())
+        .retry::<10>()
+        .backoff(Constant::new(DELAY))
+        .sleeper(tokio::time::sleep)
+        .deadline(deadline)
+        .on_exhausted(|_: &usize, attempts| made = attempts)
+        .with_retry_error()
+        .await;
+    assert_eq!(result, Err(RetryError::TimedOut));
+    assert_eq!(made, 3);
+    assert!(Instant::now() < deadline);
+
+    let result = ready(failing())
+        .retry::<10>()
+        .backoff(Constant::new(DELAY))
+        .sleeper(tokio::time::sleep)
+        .deadline(Instant::now())
+        .await;
+    assert_eq!(result, Err(1));
+}
+
+#[cfg(all(feature = "builder", feature = "futures"))]
+#[tokio::test]
+async fn async_builder_stops_before_sleeping_past_the_deadline() {
+    use retry::builder::future::*;
+
+    let deadline = Instant::now() + LIMIT;
+    let result = ready(failing())
+        .retry(10)
+        .backoff(Constant::new(DELAY))
+        .sleeper(tokio::time::sleep)
+        .deadline(deadline)
+        .with_retry_error()
+        .run()
+        .await;
+    assert_eq!(result, Err(RetryError::TimedOut));
+    assert!(Instant::now() < deadline);
+}
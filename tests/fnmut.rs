@@ -0,0 +1,94 @@
+mod common;
+
+/// [`common::flaky`] taking an argument, succeeding with it and the number
+/// of calls
+fn flaky(fails: usize) -> impl FnMut(i32) -> Result<(i32, usize), usize> {
+    let mut flaky = common::flaky(fails);
+    move |x| flaky().map(|calls| (x * 2, calls))
+}
+
+#[test]
+fn oneshot() {
+    use retry::prelude::*;
+
+    assert_eq!(flaky(2).retry::<3>(4), Ok((8, 3)));
+    assert_eq!(flaky(0).repeat::<3>(4), Ok((8, 3)));
+}
+
+#[cfg(feature = "builder")]
+#[test]
+fn builder() {
+    use retry::builder::{repeat::*, retry::*, run::*};
+
+    assert_eq!(flaky(2).retry(3).run(4), Ok((8, 3)));
+    assert_eq!(flaky(0).repeat(3).run(4), Ok((8, 3)));
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn future() {
+    use retry::future::repeat::*;
+    use retry::future::retry::*;
+
+    let mut calls = 0;
+    let mut seen = Vec::new();
+    let result = (|x: i32| {
+        calls += 1;
+        seen.push(x);
+        let ok = calls > 2;
+        async move {
+            if ok {
+                Ok(x)
+            } else {
+                Err(x)
+            }
+        }
+    })
+    .retry::<3>(5)
+    .await;
+    assert_eq!(result, Ok::<_, i32>(5));
+    assert_eq!(seen, [5, 5, 5]);
+
+    let mut calls = 0;
+    (|x: i32| {
+        calls += 1;
+        async move { x }
+    })
+    .repeat::<3>(1)
+    .await;
+    assert_eq!(calls, 3);
+}
+
+#[cfg(all(feature = "builder", feature = "futures"))]
+#[tokio::test]
+async fn async_builder() {
+    use retry::builder::future::*;
+
+    let mut calls = 0;
+    let result = (|x: i32| {
+        calls += 1;
+        let ok = calls > 1;
+        async move {
+            if ok {
+                Ok(x)
+            } else {
+                Err(x)
+            }
+        }
+    })
+    .retry(3)
+    .run(7)
+    .await;
+    assert_eq!(result, Ok::<_, i32>(7));
+    assert_eq!(calls, 2);
+
+    let mut calls = 0;
+    (|| {
+        calls += 1;
+        async {}
+    })
+    .repeat(2)
+    .run()
+    .await;
+    assert_eq!(calls, 2);
+}
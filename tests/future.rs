@@ -0,0 +1,230 @@
+#![cfg(feature = "futures")]
+
+mod common;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+use retry::future::repeat::*;
+use retry::future::retry::*;
+
+#[derive(Default)]
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// A future that only completes once the test hands it a value and wakes it.
+#[derive(Default)]
+struct Manual {
+    value: Option<Result<u32, ()>>,
+    waker: Option<Waker>,
+    polls: usize,
+}
+
+#[derive(Clone, Default)]
+struct Handle(Arc<Mutex<Manual>>);
+
+impl Handle {
+    fn complete(&self, value: Result<u32, ()>) {
+        let mut inner = self.0.lock().unwrap();
+        inner.value = Some(value);
+        if let Some(waker) = inner.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn polls(&self) -> usize {
+        self.0.lock().unwrap().polls
+    }
+}
+
+impl Future for Handle {
+    type Output = Result<u32, ()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.0.lock().unwrap();
+        inner.polls += 1;
+        match inner.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                inner.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[test]
+fn retrier_waits_for_inner_wakeup() {
+    let handle = Handle::default();
+    let wakes = Arc::new(CountingWaker::default());
+    let waker = Waker::from(wakes.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let attempt = handle.clone();
+    let mut fut = Box::pin((move || attempt.clone()).retry::<2>());
+
+    assert!(fut.as_mut().poll(&mut cx).is_pending());
+    assert!(fut.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 0);
+    assert_eq!(handle.polls(), 2);
+
+    // A failed attempt is replaced and polled within the same call
+    handle.complete(Err(()));
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+    assert!(fut.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(handle.polls(), 4);
+
+    handle.complete(Ok(7));
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(7)));
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 2);
+    assert_eq!(handle.polls(), 5);
+}
+
+#[test]
+fn repeater_waits_for_inner_wakeup() {
+    let handle = Handle::default();
+    let wakes = Arc::new(CountingWaker::default());
+    let waker = Waker::from(wakes.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let attempt = handle.clone();
+    let mut fut = Box::pin((move || attempt.clone()).repeat::<2>());
+
+    assert!(fut.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 0);
+
+    handle.complete(Ok(1));
+    assert!(fut.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(handle.polls(), 3);
+
+    handle.complete(Ok(2));
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(2)));
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn retry_if_stops_on_permanent_error() {
+    let calls = AtomicUsize::new(0);
+    let validate = || {
+        calls.fetch_add(1, Ordering::SeqCst);
+        async { Err::<(), _>("invalid") }
+    };
+    let result = validate
+        .retry::<3>()
+        .retry_if(|e: &&str| *e != "invalid")
+        .with_retry_error()
+        .await;
+    assert_eq!(result, Err(retry::RetryError::Permanent("invalid")));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn with_attempt_sees_number_and_previous_error() {
+    use retry::attempt::{with_attempt, Attempt};
+
+    let seen = Mutex::new(Vec::new());
+    let fetch = with_attempt(|attempt: Attempt<String>| {
+        let number = attempt.number;
+        seen.lock()
+            .unwrap()
+            .push((number, attempt.previous_error.cloned()));
+        async move {
+            if number < 3 {
+                Err(format!("failure {number}"))
+            } else {
+                Ok(number)
+            }
+        }
+    });
+    assert_eq!(fetch.retry::<3>().await, Ok(3));
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            (1, None),
+            (2, Some("failure 1".to_string())),
+            (3, Some("failure 2".to_string())),
+        ]
+    );
+}
+
+/// [`common::flaky`] logging every attempt in `events`
+fn logged(
+    fails: usize,
+    events: &Arc<Mutex<Vec<String>>>,
+) -> impl FnMut() -> std::future::Ready<Result<usize, usize>> {
+    let events = events.clone();
+    let mut flaky = common::flaky(fails);
+    move || {
+        let result = flaky();
+        let (Ok(call) | Err(call)) = result;
+        events.lock().unwrap().push(format!("attempt {call}"));
+        std::future::ready(result)
+    }
+}
+
+#[tokio::test]
+async fn async_hooks_run_in_order() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let log = |events: &Arc<Mutex<Vec<String>>>, line: String| {
+        let events = events.clone();
+        async move {
+            // The retrier waits for the hook before going on
+            tokio::task::yield_now().await;
+            events.lock().unwrap().push(line);
+        }
+    };
+
+    let result = logged(2, &events)
+        .retry::<3>()
+        .on_retry_async(|e: &usize, attempt, _| {
+            log(&events, format!("retry {attempt}: failure {e}"))
+        })
+        .on_success_async(|attempt| log(&events, format!("success {attempt}")))
+        .await;
+    assert_eq!(result, Ok(3));
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            "attempt 1",
+            "retry 1: failure 1",
+            "attempt 2",
+            "retry 2: failure 2",
+            "attempt 3",
+            "success 3",
+        ]
+    );
+
+    events.lock().unwrap().clear();
+    let result = logged(5, &events)
+        .retry::<1>()
+        .on_retry(|e: &usize, attempt, _| {
+            events
+                .lock()
+                .unwrap()
+                .push(format!("retry {attempt}: failure {e}"))
+        })
+        .on_exhausted_async(|e: &usize, attempts| {
+            log(&events, format!("exhausted {attempts}: failure {e}"))
+        })
+        .await;
+    assert_eq!(result, Err(2));
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            "attempt 1",
+            "retry 1: failure 1",
+            "attempt 2",
+            "exhausted 2: failure 2",
+        ]
+    );
+}
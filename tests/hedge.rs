@@ -0,0 +1,94 @@
+#![cfg(feature = "futures")]
+
+use core::time::Duration;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use retry::tuple::*;
+
+const STAGGER: Duration = Duration::from_millis(10);
+
+/// Sets its flag when dropped before being disarmed
+struct Cancelled(Arc<AtomicBool>);
+
+impl Drop for Cancelled {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// An attempt that resolves to `output` after `delay`, setting `cancelled`
+/// if it's dropped before then
+async fn attempt(
+    delay: Duration,
+    output: Result<usize, usize>,
+    cancelled: Arc<AtomicBool>,
+) -> Result<usize, usize> {
+    let guard = Cancelled(cancelled);
+    tokio::time::sleep(delay).await;
+    core::mem::forget(guard);
+    output
+}
+
+#[tokio::test]
+async fn fast_second_attempt_wins() {
+    let calls = AtomicUsize::new(0);
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let (value, won) = (|n: usize| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        let delay = if n == 1 {
+            Duration::from_secs(60)
+        } else {
+            Duration::ZERO
+        };
+        attempt(delay, Ok(n), cancelled.clone())
+    })
+    .hedge::<3>(STAGGER, args_fn(|n| (n,)))
+    .sleeper(tokio::time::sleep)
+    .await;
+    assert_eq!((value, won), (Ok(2), 2));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    // The first attempt was dropped without finishing
+    assert!(cancelled.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn every_attempt_failing_resolves_to_the_last_failure() {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let (value, number) = (|n: usize| attempt(Duration::ZERO, Err(n), cancelled.clone()))
+        .hedge::<3>(STAGGER, args_fn(|n| (n,)))
+        .sleeper(tokio::time::sleep)
+        .await;
+    assert_eq!((value, number), (Err(3), 3));
+
+    // The slow first attempt is the last one to fail
+    let (value, number) = (|n: usize| {
+        let delay = if n == 1 { 5 * STAGGER } else { Duration::ZERO };
+        attempt(delay, Err(n), cancelled.clone())
+    })
+    .hedge::<3>(STAGGER, args_fn(|n| (n,)))
+    .sleeper(tokio::time::sleep)
+    .await;
+    assert_eq!((value, number), (Err(1), 1));
+    assert!(!cancelled.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn single_attempt_never_hedges() {
+    let calls = AtomicUsize::new(0);
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let (value, number) = (|| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        attempt(5 * STAGGER, Err(0), cancelled.clone())
+    })
+    .hedge::<1>(STAGGER, ())
+    .sleeper(tokio::time::sleep)
+    .await;
+    assert_eq!((value, number), (Err(0), 1));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let (value, number) = (|key: &'static str| async move { Ok::<_, ()>(key.len()) })
+        .hedge_n(0, STAGGER, ("key",))
+        .await;
+    assert_eq!((value, number), (Ok(3), 1));
+}
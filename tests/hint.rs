@@ -0,0 +1,231 @@
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use retry::hint::RetryAfter;
+#[cfg(any(feature = "builder", feature = "futures"))]
+use retry::hint::RetryHint;
+
+#[test]
+fn parses_seconds_and_every_date_format() {
+    assert_eq!(
+        RetryAfter::parse(" 120 "),
+        Some(RetryAfter::Delay(Duration::from_secs(120)))
+    );
+    let date = RetryAfter::Date(UNIX_EPOCH + Duration::from_secs(784_111_777));
+    for value in [
+        "Sun, 06 Nov 1994 08:49:37 GMT",
+        "Sunday, 06-Nov-94 08:49:37 GMT",
+        "Sun Nov  6 08:49:37 1994",
+    ] {
+        assert_eq!(RetryAfter::parse(value), Some(date), "{value}");
+    }
+    assert!(RetryAfter::parse("Sat, 29 Feb 2020 00:00:00 GMT").is_some());
+    assert!(RetryAfter::parse("Tue, 29 Feb 2000 00:00:00 GMT").is_some());
+}
+
+#[test]
+fn too_many_seconds_saturate() {
+    assert_eq!(
+        RetryAfter::parse(&u64::MAX.to_string()),
+        Some(RetryAfter::Delay(Duration::from_secs(u64::MAX)))
+    );
+    assert_eq!(
+        RetryAfter::parse("18446744073709551616"),
+        Some(RetryAfter::Delay(Duration::MAX))
+    );
+}
+
+#[test]
+fn rejects_invalid_dates() {
+    for value in [
+        "",
+        "-1",
+        "Sun, 31 Feb 2025 08:49:37 GMT",
+        "Wed, 29 Feb 2023 08:49:37 GMT",
+        "Thu, 29 Feb 1900 08:49:37 GMT",
+        "Mon, 31 Apr 2024 08:49:37 GMT",
+        "Foo, 06 Nov 1994 08:49:37 GMT",
+        "Sunday, 06 Nov 1994 08:49:37 GMT",
+        "Sun, 06-Nov-94 08:49:37 GMT",
+        "xx Nov 6 08:49:37 1994",
+        "Sun, 06 Nov 1994 24:00:00 GMT",
+        "Sun, 06 Nov 1994 08:49:37 UTC",
+        "Sun, 06 Nov -999 08:49:37 GMT",
+        "Sun, 06 Nov +994 08:49:37 GMT",
+        "Sunday, 06-Nov-+4 08:49:37 GMT",
+        "Sun Nov  6 08:49:37 -999",
+    ] {
+        assert_eq!(RetryAfter::parse(value), None, "{value}");
+    }
+}
+
+#[test]
+fn dates_in_the_past_wait_for_nothing() {
+    let date = RetryAfter::parse("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+    let now = UNIX_EPOCH + Duration::from_secs(784_111_700);
+    assert_eq!(date.delay_from(now), Duration::from_secs(77));
+    assert_eq!(date.delay_from(SystemTime::now()), Duration::ZERO);
+}
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+#[derive(Debug, PartialEq)]
+enum Error {
+    TooManyRequests(Duration),
+    Unavailable,
+    Invalid,
+}
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+impl RetryHint for Error {
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::TooManyRequests(delay) => Some(*delay),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+const HINT: Duration = Duration::from_millis(5);
+#[cfg(any(feature = "builder", feature = "futures"))]
+const BACKOFF: Duration = Duration::from_millis(1);
+
+/// Asks to wait, then fails without a hint, then with an error not worth
+/// retrying
+#[cfg(any(feature = "builder", feature = "futures"))]
+fn responses() -> impl FnMut() -> Result<(), Error> {
+    let mut calls = 0;
+    move || {
+        calls += 1;
+        Err(match calls {
+            1 => Error::TooManyRequests(HINT),
+            2 => Error::Unavailable,
+            _ => Error::Invalid,
+        })
+    }
+}
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+fn retryable(e: &Error) -> bool {
+    *e != Error::Invalid
+}
+
+/// Asks to wait twice, then succeeds
+#[cfg(any(feature = "builder", feature = "futures"))]
+fn busy() -> impl FnMut() -> Result<usize, Error> {
+    let mut calls = 0;
+    move || {
+        calls += 1;
+        if calls < 3 {
+            Err(Error::TooManyRequests(HINT))
+        } else {
+            Ok(calls)
+        }
+    }
+}
+
+/// Long enough to tell apart from the hint
+#[cfg(any(feature = "builder", feature = "futures"))]
+const SLOW: Duration = Duration::from_secs(1);
+
+#[cfg(feature = "builder")]
+#[test]
+fn sync_hint_replaces_the_backoff() {
+    use retry::backoff::Constant;
+    use retry::builder::{retry::*, run::*};
+    use std::time::Instant;
+
+    let started = Instant::now();
+    let result = busy()
+        .retry(5)
+        .backoff(Constant::new(SLOW))
+        .retry_hint()
+        .run();
+    assert_eq!(result, Ok(3));
+    assert!(started.elapsed() >= 2 * HINT);
+    assert!(started.elapsed() < SLOW);
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn async_hint_replaces_the_backoff() {
+    use retry::backoff::Constant;
+    use retry::future::retry::*;
+    use std::time::Instant;
+
+    let mut respond = busy();
+    let started = Instant::now();
+    let result = (move || std::future::ready(respond()))
+        .retry::<5>()
+        .backoff(Constant::new(SLOW))
+        .sleeper(tokio::time::sleep)
+        .retry_hint()
+        .await;
+    assert_eq!(result, Ok(3));
+    assert!(started.elapsed() >= 2 * HINT);
+    assert!(started.elapsed() < SLOW);
+}
+
+#[cfg(feature = "builder")]
+#[test]
+fn sync_hint_and_predicate_in_either_order() {
+    use retry::backoff::Constant;
+    use retry::builder::{retry::*, run::*};
+
+    let mut delays = Vec::new();
+    let result = responses()
+        .retry(5)
+        .backoff(Constant::new(BACKOFF))
+        .retry_hint()
+        .retry_if(retryable)
+        .on_retry(|_: &Error, _, delay| delays.push(delay))
+        .run();
+    assert_eq!(result, Err(Error::Invalid));
+    assert_eq!(delays, [HINT, BACKOFF]);
+
+    let mut delays = Vec::new();
+    let result = responses()
+        .retry(5)
+        .backoff(Constant::new(BACKOFF))
+        .retry_if(retryable)
+        .retry_hint()
+        .on_retry(|_: &Error, _, delay| delays.push(delay))
+        .run();
+    assert_eq!(result, Err(Error::Invalid));
+    assert_eq!(delays, [HINT, BACKOFF]);
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn async_hint_and_predicate_in_either_order() {
+    use retry::backoff::Constant;
+    use retry::future::retry::*;
+    use std::time::Instant;
+
+    let mut respond = responses();
+    let mut delays = Vec::new();
+    let started = Instant::now();
+    let result = (move || std::future::ready(respond()))
+        .retry::<5>()
+        .backoff(Constant::new(BACKOFF))
+        .sleeper(tokio::time::sleep)
+        .retry_hint()
+        .retry_if(retryable)
+        .on_retry(|_: &Error, _, delay| delays.push(delay))
+        .await;
+    assert_eq!(result, Err(Error::Invalid));
+    assert_eq!(delays, [HINT, BACKOFF]);
+    assert!(started.elapsed() >= HINT + BACKOFF);
+
+    let mut respond = responses();
+    let mut delays = Vec::new();
+    let result = (move || std::future::ready(respond()))
+        .retry::<5>()
+        .backoff(Constant::new(BACKOFF))
+        .retry_if(retryable)
+        .retry_hint()
+        .on_retry(|_: &Error, _, delay| delays.push(delay))
+        .await;
+    assert_eq!(result, Err(Error::Invalid));
+    assert_eq!(delays, [HINT, BACKOFF]);
+}
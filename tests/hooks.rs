@@ -0,0 +1,103 @@
+#![cfg(feature = "builder")]
+
+mod common;
+
+use core::time::Duration;
+use std::sync::Arc;
+
+use common::failing;
+use retry::backoff::Constant;
+use retry::budget::RetryBudget;
+use retry::circuit::CircuitBreaker;
+use retry::RetryError;
+
+#[test]
+fn sync_gives_up_with_a_hook() {
+    use retry::builder::{retry::*, run::*};
+
+    let mut given_up = Vec::new();
+    let result = failing()
+        .retry(5)
+        .backoff(Constant::new(Duration::from_secs(60)))
+        .max_elapsed(Duration::from_secs(1))
+        .on_exhausted(|e: &usize, attempts| given_up.push((*e, attempts)))
+        .with_retry_error()
+        .run();
+    assert_eq!(result, Err(RetryError::TimedOut));
+    assert_eq!(given_up, [(1, 1)]);
+
+    let mut given_up = Vec::new();
+    let _ = failing()
+        .retry(5)
+        .retry_if(|_: &usize| false)
+        .on_exhausted(|_: &usize, attempts| given_up.push(attempts))
+        .run();
+    let budget = Arc::new(RetryBudget::new(1, 0.0));
+    let _ = failing()
+        .retry(5)
+        .budget(budget)
+        .on_exhausted(|_: &usize, attempts| given_up.push(attempts))
+        .run();
+    assert_eq!(given_up, [1, 2]);
+}
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Unavailable,
+    CircuitOpen,
+}
+
+impl From<retry::error::CircuitOpen> for Error {
+    fn from(_: retry::error::CircuitOpen) -> Self {
+        Error::CircuitOpen
+    }
+}
+
+#[test]
+fn sync_refused_attempt_gives_up_with_a_hook() {
+    use retry::builder::{retry::*, run::*};
+
+    let breaker = Arc::new(CircuitBreaker::consecutive_failures(
+        1,
+        Duration::from_secs(60),
+    ));
+    let mut given_up = Vec::new();
+    let mut retrier = (|| Err::<(), _>(Error::Unavailable))
+        .retry(3)
+        .circuit_breaker(breaker)
+        .on_exhausted(|e: &Error, attempts| given_up.push(format!("{e:?} {attempts}")));
+    assert_eq!(retrier.run(), Err(Error::Unavailable));
+    assert_eq!(retrier.run(), Err(Error::CircuitOpen));
+    drop(retrier);
+    assert_eq!(given_up, ["Unavailable 1", "CircuitOpen 0"]);
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn async_gives_up_with_a_hook() {
+    use retry::future::retry::*;
+
+    let mut given_up = Vec::new();
+    let result = common::ready(failing())
+        .retry::<5>()
+        .backoff(Constant::new(Duration::from_secs(60)))
+        .sleeper(tokio::time::sleep)
+        .max_elapsed(Duration::from_secs(1))
+        .on_exhausted(|e: &usize, attempts| given_up.push((*e, attempts)))
+        .with_retry_error()
+        .await;
+    assert_eq!(result, Err(RetryError::TimedOut));
+    assert_eq!(given_up, [(1, 1)]);
+
+    let given_up = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let log = given_up.clone();
+    let _ = common::ready(failing())
+        .retry::<5>()
+        .retry_if(|_: &usize| false)
+        .on_exhausted_async(move |_: &usize, attempts| {
+            let log = log.clone();
+            async move { log.lock().unwrap().push(attempts) }
+        })
+        .await;
+    assert_eq!(*given_up.lock().unwrap(), [1]);
+}
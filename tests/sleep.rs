@@ -0,0 +1,96 @@
+#![cfg(feature = "futures")]
+
+mod common;
+
+use core::time::Duration;
+use std::time::Instant;
+
+use common::{flaky, ready};
+use retry::backoff::Constant;
+use retry::future::retry::*;
+use retry::future::sleep::{Delay, Sleeper, ThreadSleeper};
+
+const DELAY: Duration = Duration::from_millis(20);
+
+async fn waits_between_attempts<S: Sleeper>(sleeper: S) {
+    let started = Instant::now();
+    let result = ready(flaky(2))
+        .retry::<3>()
+        .backoff(Constant::new(DELAY))
+        .sleeper(sleeper)
+        .await;
+    assert_eq!(result, Ok(3));
+    assert!(started.elapsed() >= 2 * DELAY);
+}
+
+#[tokio::test]
+async fn thread_sleeper() {
+    waits_between_attempts(ThreadSleeper).await;
+}
+
+#[tokio::test]
+async fn delays_complete_in_order() {
+    let started = Instant::now();
+    let short = Delay::new(DELAY);
+    let long = Delay::new(3 * DELAY);
+    let order = std::sync::Mutex::new(Vec::new());
+    tokio::join!(
+        async {
+            long.await;
+            order.lock().unwrap().push("long");
+        },
+        async {
+            short.await;
+            order.lock().unwrap().push("short");
+        },
+    );
+    assert_eq!(*order.lock().unwrap(), ["short", "long"]);
+    assert!(started.elapsed() >= 3 * DELAY);
+}
+
+#[tokio::test]
+async fn dropped_delay_leaves_the_others_running() {
+    let started = Instant::now();
+    // Register a delay ahead of the one awaited, then drop it
+    let dropped = tokio::time::timeout(Duration::from_millis(1), Delay::new(DELAY)).await;
+    assert!(dropped.is_err());
+    Delay::new(2 * DELAY).await;
+    assert!(started.elapsed() >= 2 * DELAY);
+}
+
+#[tokio::test]
+async fn attempt_timeout_on_the_thread_sleeper() {
+    let started = Instant::now();
+    let result = (|| async { core::future::pending::<std::io::Result<()>>().await })
+        .retry::<2>()
+        .attempt_timeout(DELAY)
+        .await;
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::TimedOut);
+    assert!(started.elapsed() >= 3 * DELAY);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_sleeper() {
+    waits_between_attempts(retry::future::sleep::TokioSleeper).await;
+}
+
+#[cfg(feature = "async-std")]
+#[test]
+fn async_std_sleeper() {
+    async_std::task::block_on(waits_between_attempts(
+        retry::future::sleep::AsyncStdSleeper,
+    ));
+}
+
+#[cfg(feature = "smol")]
+#[test]
+fn smol_sleeper() {
+    smol::block_on(waits_between_attempts(retry::future::sleep::SmolSleeper));
+}
+
+#[cfg(feature = "smol")]
+#[test]
+fn thread_sleeper_without_tokio() {
+    smol::block_on(waits_between_attempts(ThreadSleeper));
+}
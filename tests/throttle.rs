@@ -0,0 +1,141 @@
+use core::time::Duration;
+
+use retry::throttle::AdaptiveThrottle;
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// A throttle that saw `requests` requests and accepted `accepts` of them
+fn throttle(k: f64, window: Duration, requests: usize, accepts: usize) -> AdaptiveThrottle {
+    let throttle = AdaptiveThrottle::new(k, window).seed(7);
+    for _ in 0..requests {
+        throttle.allow();
+    }
+    for _ in 0..accepts {
+        throttle.record_accept();
+    }
+    throttle
+}
+
+#[test]
+fn rejection_probability_follows_the_formula() {
+    assert_eq!(throttle(2.0, WINDOW, 10, 5).rejection_probability(), 0.0);
+    assert_eq!(
+        throttle(2.0, WINDOW, 10, 2).rejection_probability(),
+        6.0 / 11.0
+    );
+    assert_eq!(
+        throttle(1.5, WINDOW, 9, 0).rejection_probability(),
+        9.0 / 10.0
+    );
+    assert_eq!(throttle(2.0, WINDOW, 0, 0).rejection_probability(), 0.0);
+}
+
+#[test]
+fn seeded_throttles_reject_alike() {
+    let draws = |throttle: AdaptiveThrottle| (0..50).map(|_| throttle.allow()).collect::<Vec<_>>();
+    let first = draws(throttle(2.0, WINDOW, 20, 0));
+    assert_eq!(first, draws(throttle(2.0, WINDOW, 20, 0)));
+    assert!(first.contains(&true) && first.contains(&false));
+    // Nothing is rejected while the backend accepts enough
+    assert!(draws(throttle(2.0, WINDOW, 20, 60))
+        .into_iter()
+        .all(|allowed| allowed));
+}
+
+#[test]
+fn old_buckets_leave_the_window() {
+    let window = Duration::from_millis(200);
+    let throttle = throttle(2.0, window, 10, 10);
+    std::thread::sleep(Duration::from_millis(120));
+    for _ in 0..10 {
+        throttle.allow();
+    }
+    assert_eq!(throttle.rejection_probability(), 0.0);
+    // Only the failing requests are left
+    std::thread::sleep(Duration::from_millis(120));
+    assert_eq!(throttle.rejection_probability(), 10.0 / 11.0);
+    std::thread::sleep(Duration::from_millis(120));
+    assert_eq!(throttle.rejection_probability(), 0.0);
+}
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+#[derive(Debug, PartialEq)]
+enum Error {
+    Unavailable,
+    Throttled,
+}
+
+#[cfg(any(feature = "builder", feature = "futures"))]
+impl From<retry::error::Throttled> for Error {
+    fn from(_: retry::error::Throttled) -> Self {
+        Error::Throttled
+    }
+}
+
+#[cfg(feature = "builder")]
+#[test]
+fn sync_retries_stop_once_throttled() {
+    use retry::builder::{retry::*, run::*};
+    use retry::RetryError;
+    use std::sync::Arc;
+
+    let throttle = Arc::new(AdaptiveThrottle::new(2.0, WINDOW).seed(7));
+    let mut calls = 0;
+    let result = (|| {
+        calls += 1;
+        Err::<(), _>(Error::Unavailable)
+    })
+    .retry(10)
+    .throttle(throttle)
+    .with_retry_error()
+    .run();
+    assert_eq!(
+        result,
+        Err(RetryError::Throttled {
+            last: Error::Unavailable,
+            attempts: calls
+        })
+    );
+    assert!(calls < 11);
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn async_retries_stop_once_throttled() {
+    use retry::future::retry::*;
+    use retry::RetryError;
+    use std::sync::Arc;
+
+    let throttle = Arc::new(AdaptiveThrottle::new(2.0, WINDOW).seed(7));
+    let mut calls = 0;
+    let result = (|| {
+        calls += 1;
+        std::future::ready(Err::<(), _>(Error::Unavailable))
+    })
+    .retry::<10>()
+    .throttle(throttle.clone())
+    .with_retry_error()
+    .await;
+    assert_eq!(
+        result,
+        Err(RetryError::Throttled {
+            last: Error::Unavailable,
+            attempts: calls
+        })
+    );
+    assert!(calls < 11);
+
+    // Refused before the first attempt
+    let result = (|| std::future::ready(Err::<(), _>(Error::Unavailable)))
+        .retry::<10>()
+        .throttle(throttle)
+        .with_retry_error()
+        .await;
+    assert_eq!(
+        result,
+        Err(RetryError::Throttled {
+            last: Error::Throttled,
+            attempts: 0
+        })
+    );
+}
@@ -0,0 +1,88 @@
+use core::ops::ControlFlow;
+
+use retry::prelude::*;
+use retry::{RetryError, Tryable};
+
+#[test]
+fn bool_fails_on_false() {
+    assert!(false.negative());
+    assert_eq!(false.error(), Some(&()));
+    assert_eq!(false.into_result(), Err(()));
+    assert!(!true.negative());
+    assert_eq!(true.error(), None);
+    assert_eq!(true.into_result(), Ok(()));
+}
+
+#[test]
+fn control_flow_fails_on_break() {
+    let stop: ControlFlow<&str, u32> = ControlFlow::Break("busy");
+    assert!(stop.negative());
+    assert_eq!(stop.error(), Some(&"busy"));
+    assert_eq!(stop.into_result(), Err("busy"));
+    let go: ControlFlow<&str, u32> = ControlFlow::Continue(7);
+    assert!(!go.negative());
+    assert_eq!(go.error(), None);
+    assert_eq!(go.into_result(), Ok(7));
+}
+
+#[cfg(unix)]
+#[test]
+fn exit_status_fails_unless_successful() {
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    let failed = ExitStatus::from_raw(1 << 8);
+    assert!(failed.negative());
+    assert_eq!(failed.error(), Some(&failed));
+    assert_eq!(failed.into_result(), Err(failed));
+    let succeeded = ExitStatus::from_raw(0);
+    assert!(!succeeded.negative());
+    assert_eq!(succeeded.error(), None);
+    assert_eq!(succeeded.into_result(), Ok(succeeded));
+}
+
+#[test]
+fn retries_false_until_true() {
+    let mut calls = 0;
+    let done = (|| {
+        calls += 1;
+        calls == 3
+    })
+    .retry::<5>();
+    assert!(done);
+    assert_eq!(calls, 3);
+
+    let result = (|| false).try_retry::<2>();
+    assert_eq!(
+        result,
+        Err(RetryError::Exhausted {
+            last: (),
+            attempts: 3
+        })
+    );
+}
+
+#[test]
+fn retries_break_until_continue() {
+    let mut calls = 0;
+    let flow = (|| {
+        calls += 1;
+        if calls < 3 {
+            ControlFlow::Break(calls)
+        } else {
+            ControlFlow::Continue("done")
+        }
+    })
+    .retry::<5>();
+    assert_eq!(flow, ControlFlow::Continue("done"));
+    assert_eq!(calls, 3);
+
+    let result = (|| ControlFlow::<&str, ()>::Break("busy")).try_retry::<1>();
+    assert_eq!(
+        result,
+        Err(RetryError::Exhausted {
+            last: "busy",
+            attempts: 2
+        })
+    );
+}